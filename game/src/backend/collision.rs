@@ -0,0 +1,346 @@
+//! This File contains the collision system with the shapes of everything the player can run into or interact with
+//! and a spatial index to find the shapes near the player quickly.
+use ggez::glam::Vec2;
+use ggez::graphics::Rect;
+use std::collections::HashMap;
+
+/// Contains the size of a cell of the spatial index (in px).
+const CELL_SIZE: f32 = 160.;
+
+/// The shape of something in the station
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Shape {
+    Rect(Rect),
+    Circle {
+        center: Vec2,
+        radius: f32,
+    },
+    /// A convex polygon given by its corners in clockwise or counterclockwise order
+    Polygon(Vec<Vec2>),
+}
+
+impl From<Rect> for Shape {
+    fn from(rect: Rect) -> Self {
+        Shape::Rect(rect)
+    }
+}
+
+impl Shape {
+    /// Returns the smallest rectangle which contains the whole shape
+    pub(crate) fn bounds(&self) -> Rect {
+        match self {
+            Shape::Rect(rect) => *rect,
+            Shape::Circle { center, radius } => Rect::new(
+                center.x - radius,
+                center.y - radius,
+                radius * 2.,
+                radius * 2.,
+            ),
+            Shape::Polygon(corners) => {
+                let min = corners
+                    .iter()
+                    .copied()
+                    .reduce(Vec2::min)
+                    .unwrap_or_default();
+                let max = corners
+                    .iter()
+                    .copied()
+                    .reduce(Vec2::max)
+                    .unwrap_or_default();
+                Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+            }
+        }
+    }
+
+    /// Returns the center of the shape
+    pub(crate) fn center(&self) -> Vec2 {
+        match self {
+            Shape::Circle { center, .. } => *center,
+            _ => self.bounds().center().into(),
+        }
+    }
+
+    /// Returns the corners of a rectangle or polygon, circles have no corners
+    fn corners(&self) -> Vec<Vec2> {
+        match self {
+            Shape::Rect(rect) => vec![
+                Vec2::new(rect.left(), rect.top()),
+                Vec2::new(rect.right(), rect.top()),
+                Vec2::new(rect.right(), rect.bottom()),
+                Vec2::new(rect.left(), rect.bottom()),
+            ],
+            Shape::Circle { .. } => vec![],
+            Shape::Polygon(corners) => corners.clone(),
+        }
+    }
+
+    /// Returns whether the given point lies inside of the shape
+    /// # Arguments
+    /// * `point` - The point in px
+    pub(crate) fn contains(&self, point: Vec2) -> bool {
+        match self {
+            Shape::Rect(rect) => rect.contains(point),
+            Shape::Circle { center, radius } => center.distance(point) <= *radius,
+            Shape::Polygon(corners) => {
+                // the point is inside if it lies on the same side of every edge
+                let sides = edges(corners)
+                    .map(|(start, end)| (end - start).perp_dot(point - start))
+                    .collect::<Vec<_>>();
+                !sides.is_empty()
+                    && (sides.iter().all(|side| *side >= 0.)
+                        || sides.iter().all(|side| *side <= 0.))
+            }
+        }
+    }
+
+    /// Returns the point of the shape which is closest to the given point, points inside of the shape are returned unchanged
+    /// # Arguments
+    /// * `point` - The point in px
+    pub(crate) fn closest_point(&self, point: Vec2) -> Vec2 {
+        if self.contains(point) {
+            return point;
+        }
+        match self {
+            Shape::Rect(rect) => point.clamp(
+                Vec2::new(rect.left(), rect.top()),
+                Vec2::new(rect.right(), rect.bottom()),
+            ),
+            Shape::Circle { center, radius } => *center + (point - *center).normalize() * *radius,
+            Shape::Polygon(corners) => edges(corners)
+                .map(|(start, end)| closest_on_segment(start, end, point))
+                .min_by(|a, b| a.distance(point).total_cmp(&b.distance(point)))
+                .unwrap_or(point),
+        }
+    }
+
+    /// Returns the distance between the given point and the shape, it is zero for points inside of the shape
+    /// # Arguments
+    /// * `point` - The point in px
+    pub(crate) fn distance_to(&self, point: Vec2) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+
+    /// Returns whether the two shapes overlap, shapes which only touch each other do not overlap
+    /// # Arguments
+    /// * `other` - The other shape
+    pub(crate) fn overlaps(&self, other: &Shape) -> bool {
+        match (self, other) {
+            (Shape::Rect(a), Shape::Rect(b)) => {
+                a.left() < b.right()
+                    && a.right() > b.left()
+                    && a.top() < b.bottom()
+                    && a.bottom() > b.top()
+            }
+            (
+                Shape::Circle {
+                    center: a,
+                    radius: radius_a,
+                },
+                Shape::Circle {
+                    center: b,
+                    radius: radius_b,
+                },
+            ) => a.distance(*b) < radius_a + radius_b,
+            (Shape::Circle { center, radius }, shape)
+            | (shape, Shape::Circle { center, radius }) => shape.distance_to(*center) < *radius,
+            _ => !is_separated(&self.corners(), &other.corners()),
+        }
+    }
+}
+
+/// Returns every edge of a polygon as its start and end corner
+fn edges(corners: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    corners
+        .iter()
+        .copied()
+        .zip(corners.iter().copied().cycle().skip(1))
+}
+
+/// Returns the point on the line segment from `start` to `end` which is closest to the given point
+fn closest_on_segment(start: Vec2, end: Vec2, point: Vec2) -> Vec2 {
+    let segment = end - start;
+    let length = segment.length_squared();
+    if length == 0. {
+        return start;
+    }
+    start + segment * ((point - start).dot(segment) / length).clamp(0., 1.)
+}
+
+/// Returns whether two convex polygons are separated by a line, which is the case if they do not overlap.
+/// Polygons which only touch each other are separated.
+fn is_separated(a: &[Vec2], b: &[Vec2]) -> bool {
+    edges(a).chain(edges(b)).any(|(start, end)| {
+        let axis = (end - start).perp();
+        let project = |corners: &[Vec2]| {
+            corners
+                .iter()
+                .map(|corner| corner.dot(axis))
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                })
+        };
+        let ((min_a, max_a), (min_b, max_b)) = (project(a), project(b));
+        max_a <= min_b || max_b <= min_a
+    })
+}
+
+/// Finds the shapes which overlap an area without checking every shape.
+/// Every shape is sorted into the cells of a grid it covers, so only the shapes in the cells of the area are checked.
+#[derive(Debug, Clone)]
+pub(crate) struct SpatialIndex<T> {
+    /// Contains the indices of the entries in every cell
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    entries: Vec<(Shape, T)>,
+}
+
+impl<T> Default for SpatialIndex<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            entries: vec![],
+        }
+    }
+}
+
+impl<T> FromIterator<(Shape, T)> for SpatialIndex<T> {
+    fn from_iter<I: IntoIterator<Item = (Shape, T)>>(iter: I) -> Self {
+        let mut index = Self::default();
+        for (shape, value) in iter {
+            index.insert(shape, value);
+        }
+        index
+    }
+}
+
+impl<T> SpatialIndex<T> {
+    /// Returns the cells of the grid the given area covers
+    fn cells_in(area: Rect) -> impl Iterator<Item = (i32, i32)> {
+        let to_cell = |value: f32| (value / CELL_SIZE).floor() as i32;
+        let (left, top) = (to_cell(area.left()), to_cell(area.top()));
+        let (right, bottom) = (to_cell(area.right()), to_cell(area.bottom()));
+        (top..=bottom).flat_map(move |row| (left..=right).map(move |column| (column, row)))
+    }
+
+    /// Adds a shape together with its value to the index
+    /// # Arguments
+    /// * `shape` - The shape
+    /// * `value` - The value which is returned by the queries that find the shape
+    pub(crate) fn insert(&mut self, shape: Shape, value: T) {
+        for cell in Self::cells_in(shape.bounds()) {
+            self.cells.entry(cell).or_default().push(self.entries.len());
+        }
+        self.entries.push((shape, value));
+    }
+
+    /// Returns every shape which overlaps the given shape together with its value
+    /// # Arguments
+    /// * `shape` - The shape to look for overlapping shapes
+    pub(crate) fn query<'a>(&'a self, shape: &Shape) -> impl Iterator<Item = &'a (Shape, T)> {
+        let mut found = Self::cells_in(shape.bounds())
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        // shapes which cover several cells are found more than once
+        found.sort_unstable();
+        found.dedup();
+        let shape = shape.clone();
+        found
+            .into_iter()
+            .map(|index| &self.entries[index])
+            .filter(move |(other, _)| other.overlaps(&shape))
+    }
+
+    /// Returns whether any shape of the index overlaps the given shape
+    /// # Arguments
+    /// * `shape` - The shape to check
+    pub(crate) fn overlaps(&self, shape: &Shape) -> bool {
+        self.query(shape).next().is_some()
+    }
+}
+
+/// Returns the value of the shape nearest to the given position.
+/// Shapes in the direction the player is facing are preferred over shapes behind them,
+/// so the player interacts with what is in front of them if several shapes are in reach.
+/// # Arguments
+/// * `position` - The position the distance is measured from, usually the center of the player
+/// * `facing` - The direction the player is facing
+/// * `candidates` - The shapes together with their values
+pub(crate) fn nearest_facing<T>(
+    position: Vec2,
+    facing: Vec2,
+    candidates: impl IntoIterator<Item = (Shape, T)>,
+) -> Option<T> {
+    candidates
+        .into_iter()
+        .map(|(shape, value)| {
+            let offset = shape.closest_point(position) - position;
+            // shapes the player stands in count as in front of them
+            let behind = offset.normalize_or_zero().dot(facing) < 0.;
+            ((behind, offset.length()), value)
+        })
+        .min_by(|((behind_a, distance_a), _), ((behind_b, distance_b), _)| {
+            behind_a
+                .cmp(behind_b)
+                .then(distance_a.total_cmp(distance_b))
+        })
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shapes() {
+        let rect = Shape::Rect(Rect::new(0., 0., 100., 50.));
+        let touching = Shape::Rect(Rect::new(100., 0., 20., 20.));
+        assert!(!rect.overlaps(&touching));
+        assert!(rect.overlaps(&Shape::Rect(Rect::new(90., 40., 20., 20.))));
+        let circle = Shape::Circle {
+            center: Vec2::new(120., 25.),
+            radius: 25.,
+        };
+        assert!(rect.overlaps(&circle) && circle.overlaps(&rect));
+        assert!(!rect.overlaps(&Shape::Circle {
+            center: Vec2::new(130., 25.),
+            radius: 25.,
+        }));
+        let triangle = Shape::Polygon(vec![
+            Vec2::new(200., 0.),
+            Vec2::new(300., 0.),
+            Vec2::new(200., 100.),
+        ]);
+        assert!(triangle.contains(Vec2::new(210., 10.)));
+        assert!(!triangle.contains(Vec2::new(290., 90.)));
+        assert!(!triangle.overlaps(&Shape::Rect(Rect::new(260., 60., 30., 30.))));
+        assert!(triangle.overlaps(&Shape::Rect(Rect::new(180., 20., 30., 30.))));
+        assert!((triangle.distance_to(Vec2::new(190., 50.)) - 10.).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_spatial_index() {
+        let index = (0..20)
+            .map(|i| (Shape::Rect(Rect::new(i as f32 * 100., 0., 50., 50.)), i))
+            .collect::<SpatialIndex<_>>();
+        let area = Shape::Rect(Rect::new(260., 10., 180., 10.));
+        let mut found = index.query(&area).map(|(_, i)| *i).collect::<Vec<_>>();
+        found.sort_unstable();
+        assert_eq!(found, vec![3, 4]);
+        assert!(!index.overlaps(&Shape::Rect(Rect::new(0., 60., 2000., 10.))));
+
+        // The shape in front of the player is preferred over a nearer one behind them
+        let candidates = [
+            (Shape::Rect(Rect::new(-40., 0., 30., 30.)), "behind"),
+            (Shape::Rect(Rect::new(20., 0., 30., 30.)), "in front"),
+        ];
+        assert_eq!(
+            nearest_facing(Vec2::ZERO, Vec2::X, candidates.clone()),
+            Some("in front")
+        );
+        assert_eq!(
+            nearest_facing(Vec2::ZERO, Vec2::NEG_X, candidates),
+            Some("behind")
+        );
+    }
+}
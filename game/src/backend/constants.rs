@@ -1,11 +1,20 @@
 //! Contains all constants that are necessary for the game to played.
 use crate::backend::rlcolor::RLColor;
+use crate::game_core::infoscreen::DeathReason;
+use crate::game_core::item::{Item, ItemId};
 use crate::game_core::player::gen_inventory;
-use crate::game_core::resources::Resources;
-use crate::languages::{machine_names, Lang};
+use crate::game_core::resources::{
+    Critical, ResourceInfo, ResourceKind, Resources, RESOURCE_COUNT,
+};
+use crate::game_core::status_effect::StatusKind;
+use crate::languages::{
+    chain_warnings, comet_strike, difficulty_names, fire, game_mode_names, informations_popup_mars,
+    informations_popup_nasa, machine_names, power_failure, sandstorm, seismic_tremor, storm_damage,
+    trade_names, Lang,
+};
 use crate::machines::machine::{Machine, State};
-use crate::machines::trade::Trade;
-use ggez::graphics::{Color, Rect};
+use crate::machines::trade::{Trade, TradeEffect};
+use ggez::graphics::Rect;
 
 #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum PopupType {
@@ -21,17 +30,37 @@ pub enum ObjectId {
     WorkMachine = 2,
     Printer3D = 3,
     CommunicationModule = 4,
-    NorthHole = 5,
-    SouthHole = 6,
+    #[serde(alias = "NorthHole", alias = "SouthHole")]
+    Hole = 5,
+    SolarPanel = 6,
+    StorageCrate = 7,
+    Hydroponics = 8,
+    WaterRecycler = 9,
+    Bunk = 10,
 }
 
 impl ObjectId {
+    /// Contains every kind of machine
+    pub(crate) const ALL: [ObjectId; 11] = [
+        ObjectId::OxygenGenerator,
+        ObjectId::PowerGenerator,
+        ObjectId::WorkMachine,
+        ObjectId::Printer3D,
+        ObjectId::CommunicationModule,
+        ObjectId::Hole,
+        ObjectId::SolarPanel,
+        ObjectId::StorageCrate,
+        ObjectId::Hydroponics,
+        ObjectId::WaterRecycler,
+        ObjectId::Bunk,
+    ];
+
     pub fn t(self, lng: Lang) -> &'static str {
         machine_names(lng)[self as usize]
     }
 
     pub fn is_hole(self) -> bool {
-        matches!(self, ObjectId::NorthHole | ObjectId::SouthHole)
+        self == ObjectId::Hole
     }
 }
 
@@ -50,8 +79,136 @@ pub enum TradeId {
     Produce3dPart = 9,
     RepairCommunicationModule = 10,
     EmergencySignalOff = 11,
-    RepairNorthHole = 12,
-    RepairSouthHole = 13,
+    #[serde(alias = "RepairNorthHole", alias = "RepairSouthHole")]
+    RepairHole = 12,
+    UpgradeOxygen = 13,
+    UpgradePowerGenerator = 14,
+    StartSolarPanel = 15,
+    StopSolarPanel = 16,
+    SealAirlock = 17,
+    RerouteEnergy = 18,
+    RepairHydroponics = 19,
+    StartHydroponics = 20,
+    StopHydroponics = 21,
+    RepairWaterRecycler = 22,
+    StartWaterRecycler = 23,
+    StopWaterRecycler = 24,
+    RepairBunk = 25,
+    Sleep = 26,
+    DepositSuperglue = 27,
+    WithdrawSuperglue = 28,
+    DepositPetrol = 29,
+    WithdrawPetrol = 30,
+    DepositPrintedPart = 31,
+    WithdrawPrintedPart = 32,
+    DepositScrap = 33,
+    WithdrawScrap = 34,
+    RecycleScrap = 35,
+}
+
+impl TradeId {
+    pub fn t(self, lng: Lang) -> &'static str {
+        usize::try_from(self as i8).map_or("", |index| trade_names(lng)[index])
+    }
+}
+
+/// The difficulty of a game, which defines how harsh the station's environment is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum Difficulty {
+    Easy = 0,
+    #[default]
+    Normal = 1,
+    Hard = 2,
+}
+
+impl Difficulty {
+    pub fn t(self, lng: Lang) -> &'static str {
+        difficulty_names(lng)[self as usize]
+    }
+
+    /// Returns the difficulty that follows this one, wraps around after the hardest difficulty
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    /// Returns the highest number of hull breaches that can be open at the same time
+    pub fn max_breaches(self) -> usize {
+        match self {
+            Difficulty::Easy => 2,
+            Difficulty::Normal => 3,
+            Difficulty::Hard => 5,
+        }
+    }
+}
+
+/// The mode of a game, which defines how the game ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum GameMode {
+    /// The game is won by completing the objectives
+    #[default]
+    Story = 0,
+    /// The game has no win condition, the events get worse over time and the player is scored by the time survived
+    Endless = 1,
+}
+
+impl GameMode {
+    pub fn t(self, lng: Lang) -> &'static str {
+        game_mode_names(lng)[self as usize]
+    }
+
+    /// Returns the other game mode
+    pub fn next(self) -> Self {
+        match self {
+            GameMode::Story => GameMode::Endless,
+            GameMode::Endless => GameMode::Story,
+        }
+    }
+}
+
+/// Stable identifier of an event, independent of the current language
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize,
+)]
+pub enum EventId {
+    Sandstorm,
+    CometStrike,
+    PowerFailure,
+    #[default]
+    InformationNasa,
+    InformationMars,
+    SeismicTremor,
+    Fire,
+    StormDamage,
+}
+
+impl EventId {
+    /// Returns the warning which announces this event when it follows another event
+    pub fn chain_warning(self, lng: Lang) -> &'static str {
+        chain_warnings(lng)[self as usize]
+    }
+
+    /// Returns the name and the info text of this event
+    pub fn texts(self, lng: Lang) -> [&'static str; 2] {
+        match self {
+            EventId::Sandstorm => *sandstorm(lng),
+            EventId::CometStrike => *comet_strike(lng),
+            EventId::PowerFailure => *power_failure(lng),
+            EventId::InformationNasa => *informations_popup_nasa(lng),
+            EventId::InformationMars => *informations_popup_mars(lng),
+            EventId::SeismicTremor => *seismic_tremor(lng),
+            EventId::Fire => *fire(lng),
+            EventId::StormDamage => *storm_damage(lng),
+        }
+    }
+
+    /// Returns the name of this event
+    pub fn name(self, lng: Lang) -> &'static str {
+        self.texts(lng)[0]
+    }
 }
 
 /// Contains the screen resolution of the game.
@@ -62,49 +219,250 @@ pub const SCREEN_RESOLUTION: (f32, f32) = (1920., 1080.);
 /// Contains the desired FPS of the game-loop.
 pub(crate) const DESIRED_FPS: u32 = 60;
 
-/// Contains the coordinates map border( x-right, y-bottom, x-left, y-top)
-pub const MAP_BORDER: [usize; 4] = [1780, 860, 270, 220];
+/// Contains the registry of all resources, indexed by `ResourceKind`.
+/// It describes how every resource behaves and where its bar is drawn.
+/// The bars of the needs of the player are drawn on the top left, outside of the station.
+pub(crate) const RESOURCE_REGISTRY: [ResourceInfo; RESOURCE_COUNT] = [
+    ResourceInfo {
+        max: u16::MAX,
+        critical: Critical::Empty,
+        status: Some(StatusKind::Hypoxia),
+        death_reason: Some(DeathReason::Oxygen),
+        need: false,
+        position: (316.0, 961.0),
+        color: RLColor::BLUE,
+    },
+    ResourceInfo {
+        max: u16::MAX,
+        critical: Critical::Empty,
+        status: Some(StatusKind::Hypothermia),
+        death_reason: Some(DeathReason::Energy),
+        need: false,
+        position: (639.0, 961.0),
+        color: RLColor::GOLD,
+    },
+    ResourceInfo {
+        max: u16::MAX,
+        critical: Critical::Empty,
+        status: None,
+        death_reason: None,
+        need: false,
+        position: (1373.0, 961.0),
+        color: RLColor::DARK_RED,
+    },
+    ResourceInfo {
+        max: u16::MAX,
+        critical: Critical::Empty,
+        status: Some(StatusKind::Starvation),
+        death_reason: Some(DeathReason::Hunger),
+        need: true,
+        position: (40.0, 40.0),
+        color: RLColor::STATUS_GREEN,
+    },
+    ResourceInfo {
+        max: u16::MAX,
+        critical: Critical::Empty,
+        status: Some(StatusKind::Dehydration),
+        death_reason: Some(DeathReason::Thirst),
+        need: true,
+        position: (40.0, 80.0),
+        color: RLColor::LIGHT_BLUE,
+    },
+    ResourceInfo {
+        max: 54_000,
+        critical: Critical::Full,
+        status: Some(StatusKind::Exhaustion),
+        death_reason: None,
+        need: true,
+        position: (40.0, 120.0),
+        color: RLColor::LIGHT_GREY,
+    },
+];
 
-/// Contains the position of the resource bars.
-pub(crate) const RESOURCE_POSITION: [f32; 3] = [316.0, 639.0, 1373.0];
+/// Contains the width (in px) of a full resource bar.
+pub(crate) const RESOURCE_BAR_WIDTH: f32 = 285.0;
 
-/// Contains the color used for the resource bars.
-pub(crate) const COLORS: [Color; 3] = [RLColor::BLUE, RLColor::GOLD, RLColor::DARK_RED];
+/// Contains the resources the player starts with, fatigue is the only resource that starts empty.
+pub(crate) const START_RESOURCES: Resources<u16> =
+    Resources::filled(u16::MAX).with(ResourceKind::Fatigue, 0);
+
+/// Contains the items the player starts with, every other item starts with zero.
+pub(crate) const START_ITEMS: [(ItemId, i32); 2] = [(ItemId::Petrol, 3), (ItemId::PrintedPart, 1)];
+
+/// Contains the change rate of the player's resources at the start of the game.
+/// Food, water and fatigue are the needs of the player which keep changing during the whole game.
+pub(crate) const START_RESOURCES_CHANGE: Resources<i16> = Resources::filled(0)
+    .with(ResourceKind::Oxygen, -5)
+    .with(ResourceKind::Energy, -10)
+    .with(ResourceKind::Food, -2)
+    .with(ResourceKind::Water, -3)
+    .with(ResourceKind::Fatigue, 2);
 
 /// Contains the size of the player icon (in px) to scale the collision area.
-pub(crate) const PLAYER_ICON_SIZE: (usize, usize) = (58, 96);
+pub(crate) const PLAYER_ICON_SIZE: (f32, f32) = (58., 96.);
 
 /// Contains the interaction radius of the player (in px).
 pub(crate) const PLAYER_INTERACTION_RADIUS: f32 = 50.;
 
-/// Contains the movement speed of the player (in px).
-pub const MOVEMENT_SPEED: usize = 10;
+/// Contains the stack limit and the weight of every item.
+/// Items are identified by their image, since it does not depend on the language.
+pub(crate) const ITEM_PROPERTIES: [(&str, i32, u32); 4] = [
+    ("SuperGlue.png", 10, 1),
+    ("Benzin.png", 5, 4),
+    ("3D-gedrucktes-Teil.png", 8, 2),
+    ("Schrott.png", 12, 1),
+];
+
+/// Contains the weight the player can carry without being slowed down.
+pub(crate) const CARRY_CAPACITY: u32 = 20;
+
+/// Contains by how many percent every unit of weight above the carry capacity slows the player down.
+pub(crate) const OVERWEIGHT_SLOWDOWN: usize = 5;
+
+/// Contains the lowest movement speed (in percent) the player can be slowed down to by carrying too much.
+pub(crate) const MIN_CARRY_SPEED: usize = 40;
+
+/// Contains the highest movement speed of the player (in px per second).
+pub const MOVEMENT_SPEED: f32 = 600.;
+
+/// Contains how fast the player speeds up while moving (in px per second squared).
+pub(crate) const PLAYER_ACCELERATION: f32 = 6000.;
+
+/// Contains how fast the player slows down once no direction is pressed (in px per second squared).
+pub(crate) const PLAYER_DECELERATION: f32 = 8000.;
+
+/// Contains the longest distance (in px) the player moves before collisions are checked again,
+/// it is shorter than a tile so the player can not pass through walls.
+pub(crate) const PLAYER_STEP: f32 = 10.;
+
+/// Contains the longest time (in seconds) the player moves in one frame, longer frames are cut to it.
+pub(crate) const MAX_MOVEMENT_DELTA: f32 = 0.25;
 
 /// Contains the position of the time.
-pub(crate) const TIME_POSITION: (f32, f32) = (1205., 960.);
+pub(crate) const TIME_POSITION: (f32, f32) = (1225., 960.);
 
-/// Change rate fot the event Sandsturm
-pub(crate) const SANDSTORM_CR: Resources<i16> = Resources {
-    oxygen: 10,
-    energy: 0,
-    life: 0,
-};
+/// Contains the position of the first status effect icon.
+pub(crate) const STATUS_POSITION: (f32, f32) = (290., 995.);
+
+/// Contains the duration (in seconds) of status effects caused by an empty resource, they wear off after it is refilled.
+pub(crate) const CONDITION_STATUS_DURATION: u32 = 2;
+
+/// Contains the amount of life below which the player becomes exhausted.
+pub(crate) const EXHAUSTION_LIFE: u16 = u16::MAX / 4;
+
+/// Contains the position of the forecast of upcoming events.
+pub(crate) const FORECAST_POSITION: (f32, f32) = (1560., 20.);
+
+/// Contains the position of the objective tracker.
+pub(crate) const TRACKER_POSITION: (f32, f32) = (450., 20.);
+
+/// Contains the position of the guide which shows the current step of the tutorial.
+pub(crate) const TUTORIAL_POSITION: (f32, f32) = (510., 820.);
+
+/// Change rate of a hull breach with severity 1, it is multiplied by the severity of the breach.
+pub(crate) const BREACH_LEAK: Resources<i16> = Resources::filled(0)
+    .with(ResourceKind::Oxygen, -15)
+    .with(ResourceKind::Energy, -5);
+
+/// Contains the highest severity a hull breach can grow to.
+pub(crate) const BREACH_MAX_SEVERITY: u8 = 3;
+
+/// Contains the air pressure of a room which is completely filled with air.
+pub(crate) const ROOM_PRESSURE_MAX: u16 = 1000;
+
+/// Contains the air pressure a room loses per tick and severity of the hull breaches in it.
+pub(crate) const ROOM_PRESSURE_LEAK: u16 = 2;
+
+/// Contains the air pressure a room without hull breaches regains per tick.
+pub(crate) const ROOM_PRESSURE_REFILL: u16 = 1;
+
+/// Contains the position of the pressure of the room the player is standing in.
+pub(crate) const ROOM_INFO_POSITION: (f32, f32) = (40., 160.);
+
+/// Contains the pressure an open airlock lets escape per tick from the rooms connected to it.
+pub(crate) const AIRLOCK_LEAK: u16 = 10;
+
+/// Contains the oxygen of a filled suit, the suit loses one unit per tick outside of the station.
+pub(crate) const SUIT_OXYGEN_MAX: u16 = 5400;
+
+/// Contains the oxygen the suit regains per tick inside of the station, it is taken from the oxygen of the station.
+pub(crate) const SUIT_OXYGEN_REFILL: u16 = 30;
+
+/// Contains the position of the suit oxygen, it is shown below the pressure of the room.
+pub(crate) const SUIT_INFO_POSITION: (f32, f32) = (40., 185.);
+
+/// Contains the size of a scavenge point (in px).
+pub(crate) const SCAVENGE_POINT_SIZE: f32 = 60.;
+
+/// Contains the time (in seconds) a scavenge point needs until it can be scavenged again.
+pub(crate) const SCAVENGE_COOLDOWN: u32 = 180;
+
+/// Contains the time (in ticks) after which an unrepaired hull breach grows.
+pub(crate) const BREACH_GROWTH_TIME: u32 = 45 * DESIRED_FPS;
+
+/// Returns the size of the hitbox of a machine.
+/// # Arguments
+/// * `id` - The kind of the machine
+/// # Returns
+/// `(f32, f32)` - The width and height of the machine
+pub(crate) fn machine_size(id: ObjectId) -> (f32, f32) {
+    match id {
+        ObjectId::OxygenGenerator => (350.0, 182.0),
+        ObjectId::PowerGenerator => (194.0, 189.0),
+        ObjectId::WorkMachine => (300.0, 150.0),
+        ObjectId::Printer3D => (200.0, 148.0),
+        ObjectId::CommunicationModule => (175.0, 477.0),
+        ObjectId::Hole => (32.0, 18.0),
+        ObjectId::SolarPanel => (120.0, 80.0),
+        ObjectId::StorageCrate => (90.0, 70.0),
+        ObjectId::Hydroponics => (220.0, 110.0),
+        ObjectId::WaterRecycler => (140.0, 120.0),
+        ObjectId::Bunk => (200.0, 80.0),
+    }
+}
+
+/// Generates the trades that seal the airlock during a sandstorm.
+/// The airlock does not depend on the oxygen generator, so there is one trade for every state.
+/// # Returns
+/// The locked trades, one per machine state
+fn seal_airlock_trades(lng: Lang) -> Vec<Trade> {
+    [State::Broken, State::Idle, State::Running]
+        .into_iter()
+        .map(|state| {
+            Trade::new(
+                TradeId::SealAirlock,
+                60,
+                state.clone(),
+                state,
+                false,
+                gen_inventory(1, 0, 0, 0, lng),
+            )
+            .with_effects(vec![TradeEffect::MitigateEvent(EventId::Sandstorm, 50)])
+            .locked()
+        })
+        .collect()
+}
 
 #[allow(clippy::too_many_lines)]
-/// Generates all machines with all their name, position, trades and resources.
+/// Generates a machine with all its trades and resources at the given position.
+/// # Arguments
+/// * `id` - The kind of the machine
+/// * `position` - The position of the top left corner of the machine
 /// # Returns
-/// A Vector of `Machine`s
-pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
-    vec![
+/// The `Machine`
+pub(crate) fn gen_machine(id: ObjectId, position: (f32, f32), lng: Lang) -> Machine {
+    let (w, h) = machine_size(id);
+    let hit_box = Rect {
+        x: position.0,
+        y: position.1,
+        w,
+        h,
+    };
+    match id {
         // Oxygen machine
-        Machine::new_by_const((
-            ObjectId::OxygenGenerator,
-            Rect {
-                x: 280.0,
-                y: 230.0,
-                w: 350.0,
-                h: 182.0,
-            },
+        ObjectId::OxygenGenerator => Machine::new_by_const((
+            id,
+            hit_box,
             vec![
                 Trade::new(
                     TradeId::RepairOxygen,
@@ -112,7 +470,7 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Broken,
                     State::Idle,
                     false,
-                    gen_inventory(2, 0, 0, lng),
+                    gen_inventory(2, 0, 0, 0, lng),
                 ),
                 Trade::new(
                     TradeId::StartOxygen,
@@ -120,7 +478,7 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Idle,
                     State::Running,
                     true,
-                    gen_inventory(0, 0, 0, lng),
+                    gen_inventory(0, 0, 0, 0, lng),
                 ),
                 Trade::new(
                     TradeId::StopOxygen,
@@ -128,24 +486,32 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Running,
                     State::Idle,
                     true,
-                    gen_inventory(0, 0, 0, lng),
+                    gen_inventory(0, 0, 0, 0, lng),
                 ),
-            ],
-            Resources {
-                oxygen: 30,
-                energy: -30,
-                life: 0,
-            },
-        )),
+                Trade::new(
+                    TradeId::UpgradeOxygen,
+                    400,
+                    State::Idle,
+                    State::Idle,
+                    false,
+                    gen_inventory(3, 0, 1, 0, lng),
+                )
+                .with_effects(vec![TradeEffect::Upgrade]),
+            ]
+            .into_iter()
+            .chain(seal_airlock_trades(lng))
+            .collect(),
+            Resources::filled(0)
+                .with(ResourceKind::Oxygen, 30)
+                .with(ResourceKind::Energy, -30),
+        ))
+        .with_tiers(vec![Resources::filled(0)
+            .with(ResourceKind::Oxygen, 45)
+            .with(ResourceKind::Energy, -20)]),
         // Electricity machine
-        Machine::new_by_const((
-            ObjectId::PowerGenerator,
-            Rect {
-                x: 282.0,
-                y: 752.0,
-                w: 194.0,
-                h: 189.0,
-            },
+        ObjectId::PowerGenerator => Machine::new_by_const((
+            id,
+            hit_box,
             vec![
                 Trade::new(
                     TradeId::FuelingPowerGenerator,
@@ -153,7 +519,7 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Broken,
                     State::Running,
                     true,
-                    gen_inventory(0, 1, 0, lng),
+                    gen_inventory(0, 1, 0, 0, lng),
                 ),
                 Trade::new(
                     TradeId::StartPowerGenerator,
@@ -161,7 +527,7 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Idle,
                     State::Running,
                     true,
-                    gen_inventory(0, 0, 0, lng),
+                    gen_inventory(0, 0, 0, 0, lng),
                 ),
                 Trade::new(
                     TradeId::StopPowerGenerator,
@@ -169,24 +535,39 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Running,
                     State::Idle,
                     true,
-                    gen_inventory(0, 0, 0, lng),
+                    gen_inventory(0, 0, 0, 0, lng),
                 ),
+                Trade::new(
+                    TradeId::UpgradePowerGenerator,
+                    400,
+                    State::Idle,
+                    State::Idle,
+                    false,
+                    gen_inventory(2, 0, 1, 0, lng),
+                )
+                .with_effects(vec![TradeEffect::Upgrade]),
+                Trade::new(
+                    TradeId::RerouteEnergy,
+                    100,
+                    State::Idle,
+                    State::Idle,
+                    false,
+                    gen_inventory(0, 1, 0, 0, lng),
+                )
+                .with_effects(vec![TradeEffect::MitigateEvent(EventId::PowerFailure, 100)])
+                .locked(),
             ],
-            Resources {
-                oxygen: -5,
-                energy: 200,
-                life: 0,
-            },
-        )),
+            Resources::filled(0)
+                .with(ResourceKind::Oxygen, -5)
+                .with(ResourceKind::Energy, 200),
+        ))
+        .with_tiers(vec![Resources::filled(0)
+            .with(ResourceKind::Oxygen, -3)
+            .with(ResourceKind::Energy, 260)]),
         // Worker machine
-        Machine::new_by_const((
-            ObjectId::WorkMachine,
-            Rect {
-                x: 1000.0,
-                y: 780.0,
-                w: 300.0,
-                h: 150.0,
-            },
+        ObjectId::WorkMachine => Machine::new_by_const((
+            id,
+            hit_box,
             vec![
                 Trade::new(
                     TradeId::RepairWorkMachine,
@@ -194,7 +575,7 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Broken,
                     State::Idle,
                     false,
-                    gen_inventory(0, 0, 1, lng),
+                    gen_inventory(0, 0, 1, 0, lng),
                 ),
                 Trade::new(
                     TradeId::ProduceSuperglue,
@@ -202,24 +583,15 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Idle,
                     State::Running,
                     true,
-                    gen_inventory(-1, 0, 0, lng),
+                    gen_inventory(-1, 0, 0, 0, lng),
                 ),
             ],
-            Resources {
-                oxygen: 0,
-                energy: -15,
-                life: 0,
-            },
+            Resources::filled(0).with(ResourceKind::Energy, -15),
         )),
         // 3d Printer machine
-        Machine::new_by_const((
-            ObjectId::Printer3D,
-            Rect {
-                x: 930.0,
-                y: 230.0,
-                w: 200.0,
-                h: 148.0,
-            },
+        ObjectId::Printer3D => Machine::new_by_const((
+            id,
+            hit_box,
             vec![
                 Trade::new(
                     TradeId::Repair3dPrinter,
@@ -227,7 +599,7 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Broken,
                     State::Idle,
                     false,
-                    gen_inventory(2, 0, 0, lng),
+                    gen_inventory(2, 0, 0, 0, lng),
                 ),
                 Trade::new(
                     TradeId::Produce3dPart,
@@ -235,24 +607,23 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Idle,
                     State::Running,
                     true,
-                    gen_inventory(2, 0, -1, lng),
+                    gen_inventory(2, 0, -1, 0, lng),
+                ),
+                Trade::new(
+                    TradeId::RecycleScrap,
+                    150,
+                    State::Idle,
+                    State::Running,
+                    true,
+                    gen_inventory(0, 0, -1, 3, lng),
                 ),
             ],
-            Resources {
-                oxygen: 0,
-                energy: -25,
-                life: 0,
-            },
+            Resources::filled(0).with(ResourceKind::Energy, -25),
         )),
         // Communication module
-        Machine::new_by_const((
-            ObjectId::CommunicationModule,
-            Rect {
-                x: 1640.0,
-                y: 320.0,
-                w: 175.0,
-                h: 477.0,
-            },
+        ObjectId::CommunicationModule => Machine::new_by_const((
+            id,
+            hit_box,
             vec![
                 Trade::new(
                     TradeId::RepairCommunicationModule,
@@ -260,7 +631,7 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Broken,
                     State::Idle,
                     false,
-                    gen_inventory(5, 0, 3, lng),
+                    gen_inventory(5, 0, 3, 0, lng),
                 ),
                 Trade::new(
                     TradeId::EmergencySignalOff,
@@ -268,60 +639,202 @@ pub(crate) fn gen_all_machines(lng: Lang) -> Vec<Machine> {
                     State::Idle,
                     State::Running,
                     true,
-                    gen_inventory(1, 0, 1, lng),
-                ),
+                    gen_inventory(1, 0, 1, 0, lng),
+                )
+                .locked(),
             ],
-            Resources {
-                oxygen: 0,
-                energy: -30,
-                life: 0,
-            },
+            Resources::filled(0).with(ResourceKind::Energy, -30),
         )),
-        // First hole
-        Machine::new_by_const((
-            ObjectId::NorthHole,
-            Rect {
-                x: 780.0,
-                y: 230.0,
-                w: 32.0,
-                h: 18.0,
-            },
+        // Hole in the wall
+        ObjectId::Hole => Machine::new_by_const((
+            id,
+            hit_box,
             vec![Trade::new(
-                TradeId::RepairNorthHole,
+                TradeId::RepairHole,
                 100,
                 State::Running,
                 State::Idle,
                 false,
-                gen_inventory(2, 0, 0, lng),
+                gen_inventory(2, 0, 0, 0, lng),
             )],
-            Resources {
-                oxygen: -15,
-                energy: -5,
-                life: 0,
-            },
+            BREACH_LEAK,
         )),
-        // Second hole
-        Machine::new_by_const((
-            ObjectId::SouthHole,
-            Rect {
-                x: 680.0,
-                y: 900.0,
-                w: 32.0,
-                h: 18.0,
-            },
-            vec![Trade::new(
-                TradeId::RepairSouthHole,
-                100,
-                State::Running,
-                State::Idle,
-                false,
-                gen_inventory(2, 0, 0, lng),
-            )],
-            Resources {
-                oxygen: -15,
-                energy: -5,
-                life: 0,
-            },
+        // Solar panel
+        ObjectId::SolarPanel => Machine::new_by_const((
+            id,
+            hit_box,
+            vec![
+                Trade::new(
+                    TradeId::StartSolarPanel,
+                    0,
+                    State::Idle,
+                    State::Running,
+                    true,
+                    gen_inventory(0, 0, 0, 0, lng),
+                ),
+                Trade::new(
+                    TradeId::StopSolarPanel,
+                    0,
+                    State::Running,
+                    State::Idle,
+                    true,
+                    gen_inventory(0, 0, 0, 0, lng),
+                ),
+            ],
+            Resources::filled(0).with(ResourceKind::Energy, 40),
+        )),
+        // Storage locker, the player can deposit items which do not fit into the inventory
+        ObjectId::StorageCrate => Machine::new_by_const((
+            id,
+            hit_box,
+            [
+                (
+                    TradeId::DepositSuperglue,
+                    TradeId::WithdrawSuperglue,
+                    (1, 0, 0, 0),
+                ),
+                (
+                    TradeId::DepositPetrol,
+                    TradeId::WithdrawPetrol,
+                    (0, 1, 0, 0),
+                ),
+                (
+                    TradeId::DepositPrintedPart,
+                    TradeId::WithdrawPrintedPart,
+                    (0, 0, 1, 0),
+                ),
+                (TradeId::DepositScrap, TradeId::WithdrawScrap, (0, 0, 0, 1)),
+            ]
+            .into_iter()
+            .flat_map(|(deposit, withdraw, (glue, petrol, part, scrap))| {
+                [
+                    Trade::new(
+                        deposit,
+                        0,
+                        State::Idle,
+                        State::Idle,
+                        false,
+                        gen_inventory(glue, petrol, part, scrap, lng),
+                    )
+                    .with_effects(vec![TradeEffect::Deposit]),
+                    Trade::new(
+                        withdraw,
+                        0,
+                        State::Idle,
+                        State::Idle,
+                        false,
+                        gen_inventory(-glue, -petrol, -part, -scrap, lng),
+                    )
+                    .with_effects(vec![TradeEffect::Withdraw]),
+                ]
+            })
+            .collect(),
+            Resources::default(),
+        )),
+        // Hydroponics, grows food but needs water
+        ObjectId::Hydroponics => Machine::new_by_const((
+            id,
+            hit_box,
+            vec![
+                Trade::new(
+                    TradeId::RepairHydroponics,
+                    200,
+                    State::Broken,
+                    State::Idle,
+                    false,
+                    gen_inventory(1, 0, 1, 0, lng),
+                ),
+                Trade::new(
+                    TradeId::StartHydroponics,
+                    0,
+                    State::Idle,
+                    State::Running,
+                    true,
+                    gen_inventory(0, 0, 0, 0, lng),
+                ),
+                Trade::new(
+                    TradeId::StopHydroponics,
+                    0,
+                    State::Running,
+                    State::Idle,
+                    true,
+                    gen_inventory(0, 0, 0, 0, lng),
+                ),
+            ],
+            Resources::filled(0)
+                .with(ResourceKind::Oxygen, 5)
+                .with(ResourceKind::Energy, -15)
+                .with(ResourceKind::Food, 8)
+                .with(ResourceKind::Water, -2),
         )),
+        // Water recycler
+        ObjectId::WaterRecycler => Machine::new_by_const((
+            id,
+            hit_box,
+            vec![
+                Trade::new(
+                    TradeId::RepairWaterRecycler,
+                    150,
+                    State::Broken,
+                    State::Idle,
+                    false,
+                    gen_inventory(2, 0, 0, 0, lng),
+                ),
+                Trade::new(
+                    TradeId::StartWaterRecycler,
+                    0,
+                    State::Idle,
+                    State::Running,
+                    true,
+                    gen_inventory(0, 0, 0, 0, lng),
+                ),
+                Trade::new(
+                    TradeId::StopWaterRecycler,
+                    0,
+                    State::Running,
+                    State::Idle,
+                    true,
+                    gen_inventory(0, 0, 0, 0, lng),
+                ),
+            ],
+            Resources::filled(0)
+                .with(ResourceKind::Energy, -20)
+                .with(ResourceKind::Water, 9),
+        )),
+        // Bunk, the player rests while the machine is running
+        ObjectId::Bunk => Machine::new_by_const((
+            id,
+            hit_box,
+            vec![
+                Trade::new(
+                    TradeId::RepairBunk,
+                    100,
+                    State::Broken,
+                    State::Idle,
+                    false,
+                    gen_inventory(1, 0, 0, 0, lng),
+                ),
+                Trade::new(
+                    TradeId::Sleep,
+                    600,
+                    State::Idle,
+                    State::Running,
+                    true,
+                    gen_inventory(0, 0, 0, 0, lng),
+                ),
+            ],
+            Resources::filled(0).with(ResourceKind::Fatigue, -100),
+        )),
+    }
+}
+
+/// Generates all machines the player can build together with the items they cost.
+/// # Returns
+/// A Vector of the kind of machine and its cost
+pub(crate) fn gen_blueprints(lng: Lang) -> Vec<(ObjectId, Vec<(Item, i32)>)> {
+    vec![
+        (ObjectId::OxygenGenerator, gen_inventory(4, 0, 2, 0, lng)),
+        (ObjectId::SolarPanel, gen_inventory(2, 0, 2, 0, lng)),
+        (ObjectId::StorageCrate, gen_inventory(2, 0, 0, 0, lng)),
     ]
 }
@@ -1,24 +1,33 @@
 //! Contains the game logic, updates the game and draws the current board
+use crate::backend::collision::{nearest_facing, Shape, SpatialIndex};
 use crate::backend::constants::{
-    ObjectId, COLORS, DESIRED_FPS, MAP_BORDER, RESOURCE_POSITION, TIME_POSITION,
+    Difficulty, EventId, GameMode, TradeId, CARRY_CAPACITY, CONDITION_STATUS_DURATION, DESIRED_FPS,
+    EXHAUSTION_LIFE, PLAYER_INTERACTION_RADIUS, RESOURCE_BAR_WIDTH, STATUS_POSITION, TIME_POSITION,
 };
 use crate::backend::rlcolor::RLColor;
 use crate::backend::screen::{Popup, ScreenCommand, StackCommand};
 use crate::backend::utils::get_scale;
 use crate::backend::utils::{get_draw_params, is_colliding};
 use crate::backend::{error::RLError, screen::Screen};
+use crate::game_core::eva::EvaState;
 use crate::game_core::event::Event;
-use crate::game_core::infoscreen::DeathReason::{Both, Energy, Oxygen};
+use crate::game_core::infoscreen::DeathReason::{self, Injury};
 use crate::game_core::infoscreen::InfoScreen;
 use crate::game_core::item::Item;
+use crate::game_core::objective::ObjectiveProgress;
 use crate::game_core::player::Player;
-use crate::game_core::resources::Resources;
+use crate::game_core::resources::{ResourceKind, Resources};
+use crate::game_core::rooms::RoomState;
+use crate::game_core::scenario::default_scenario_id;
+use crate::game_core::scheduler::EventScheduler;
+use crate::game_core::status_effect::StatusKind;
+use crate::game_core::tutorial::TutorialProgress;
 use crate::languages::{
-    first_milestone_handbook_text, resource_name, second_milestone_handbook_text, send_msg_failure,
-    time_name, Lang,
+    carry_weight, handbook_close, survival_time, time_name, trade_conflict_popup, Lang,
 };
+use crate::machines::build_mode::BuildMode;
 use crate::machines::machine::Machine;
-use crate::machines::machine::State::Broken;
+use crate::machines::trade_panel::TradePanel;
 use crate::{draw, RLResult};
 use ggez::glam::Vec2;
 use ggez::graphics::{Canvas, Image, TextFragment};
@@ -35,8 +44,22 @@ use tracing::{debug, info};
 pub enum GameCommand {
     AddItems(Vec<(Item, i32)>),
     ResourceChange(Resources<i16>),
-    Milestone,
-    Winning,
+    TradeConcluded(TradeId),
+    TriggerEvent(EventId),
+    UnlockTrade(TradeId),
+    MitigateEvent(EventId, u8),
+    Heal(u16),
+}
+
+/// Something in the station the player can interact with by pressing E
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Interactable {
+    /// The index of a machine inside `GameState::machines`
+    Machine(usize),
+    /// The index of a door of the map
+    Door(usize),
+    /// The index of a scavenge point of the map
+    ScavengePoint(usize),
 }
 
 /// This is the game state. It contains all the data that is needed to run the game.
@@ -44,13 +67,37 @@ pub enum GameCommand {
 pub struct GameState {
     /// Contains the current player position, resources(air, energy, life) and the inventory and their change rates
     pub player: Player,
-    /// Contains the event generator and the current events
+    /// Contains the current events
     pub(crate) events: Vec<Event>,
+    /// Decides when which event occurs
+    #[serde(default)]
+    pub(crate) scheduler: EventScheduler,
+    /// The id of the scenario the game was started with
+    #[serde(default = "default_scenario_id")]
+    pub(crate) scenario: String,
+    /// Contains the objectives the player works on and has completed
+    #[serde(default)]
+    pub(crate) objectives: ObjectiveProgress,
+    /// Contains the progress in the tutorial of the scenario
+    #[serde(default)]
+    pub(crate) tutorial: TutorialProgress,
     /// Contains the machines and their current state
     pub machines: Vec<Machine>,
+    /// Contains the air pressure of the rooms and which doors are open
+    #[serde(default)]
+    pub(crate) rooms: RoomState,
+    /// Contains the state of the area outside of the station
+    #[serde(default)]
+    pub(crate) eva: EvaState,
+    /// Everything the player can run into, rebuilt by `update_collision` whenever the station changes
+    #[serde(skip)]
+    pub(crate) obstacles: SpatialIndex<()>,
+    /// Everything the player can interact with indexed by its interaction area, rebuilt together with the obstacles
+    #[serde(skip)]
+    pub(crate) interactables: SpatialIndex<(Interactable, Shape)>,
     #[serde(skip)]
     /// Contains all the images that are needed to draw the game on the canvas
-    assets: HashMap<String, Image>,
+    pub(crate) assets: HashMap<String, Image>,
     #[serde(skip)]
     /// Needed to send Messages to the `Screenstack` to make changes to the screen
     pub(crate) screen_sender: Option<Sender<StackCommand>>,
@@ -62,14 +109,29 @@ pub struct GameState {
     pub(crate) sender: Option<Sender<GameCommand>>,
     /// Defines if the handbook is currently open
     pub handbook_invisible: bool,
+    /// Defines if the objective tracker only shows its title
+    #[serde(default)]
+    pub(crate) tracker_collapsed: bool,
+    #[serde(skip)]
+    /// Contains the trade panel of the machine the player is currently interacting with
+    pub(crate) trade_panel: Option<TradePanel>,
+    #[serde(skip)]
+    /// Contains the build mode if the player is currently placing a new machine
+    pub(crate) build_mode: Option<BuildMode>,
+    /// The difficulty chosen when the game was started
+    #[serde(default)]
+    pub(crate) difficulty: Difficulty,
+    /// The mode chosen when the game was started
+    #[serde(default)]
+    pub(crate) mode: GameMode,
     #[serde(default)]
     pub lng: Lang,
 }
 
 impl PartialEq for GameState {
-    /// Compares the game state by comparing the player, events and machines
+    /// Compares the game state by comparing the player and the objectives
     fn eq(&self, other: &Self) -> bool {
-        self.player == other.player && self.player.milestone == other.player.milestone
+        self.player == other.player && self.objectives == other.objectives
     }
 }
 
@@ -78,12 +140,25 @@ impl GameState {
         Self {
             player: Player::new(lng),
             events: vec![],
+            scheduler: EventScheduler::default(),
+            scenario: default_scenario_id(),
+            objectives: ObjectiveProgress::default(),
+            tutorial: TutorialProgress::default(),
             machines: vec![],
+            rooms: RoomState::default(),
+            eva: EvaState::default(),
+            obstacles: SpatialIndex::default(),
+            interactables: SpatialIndex::default(),
             assets: HashMap::with_capacity(64),
             screen_sender: None,
             receiver: None,
             sender: None,
             handbook_invisible: false,
+            tracker_collapsed: false,
+            trade_panel: None,
+            build_mode: None,
+            difficulty: Difficulty::default(),
+            mode: GameMode::default(),
             lng,
         }
     }
@@ -115,19 +190,62 @@ impl GameState {
         let mut result = GameState {
             player: Player::new(lng),
             events: vec![],
+            scheduler: EventScheduler::default(),
+            scenario: default_scenario_id(),
+            objectives: ObjectiveProgress::default(),
+            tutorial: TutorialProgress::default(),
             machines: vec![],
+            rooms: RoomState::default(),
+            eva: EvaState::default(),
+            obstacles: SpatialIndex::default(),
+            interactables: SpatialIndex::default(),
             assets: HashMap::with_capacity(64),
             sender: Some(sender),
             receiver: Some(receiver),
             lng,
             screen_sender: None,
             handbook_invisible: false,
+            tracker_collapsed: false,
+            trade_panel: None,
+            build_mode: None,
+            difficulty: Difficulty::default(),
+            mode: GameMode::default(),
         };
         result.init(ctx)?;
         Ok(result)
     }
+    /// Returns the current change rate of the player's resources.
+    /// It is recomputed every tick from the change rate of the machines and the modifiers of all active events.
+    pub(crate) fn resources_change(&self) -> Resources<i16> {
+        self.events
+            .iter()
+            .map(Event::resource_modifier)
+            .fold(self.player.resources_change, |sum, modifier| sum + modifier)
+    }
+
+    /// Applies the status effect of every critical resource to the player.
+    /// Machines which need energy are stopped if there is no energy left.
+    /// # Returns
+    /// * `Option<DeathReason>` - The reason of death if the player dies because of a critical resource
+    fn apply_conditions(&mut self) -> Option<DeathReason> {
+        let critical = self.player.resources.critical().collect::<Vec<_>>();
+        for kind in &critical {
+            if let Some(status) = kind.info().status {
+                self.player.add_status(status, CONDITION_STATUS_DURATION);
+            }
+        }
+        if critical.contains(&ResourceKind::Energy) {
+            self.machines.iter_mut().for_each(Machine::no_energy);
+        }
+        if self.player.resources[ResourceKind::Life] < EXHAUSTION_LIFE {
+            self.player
+                .add_status(StatusKind::Exhaustion, CONDITION_STATUS_DURATION);
+        }
+        self.player.resources.get_death_reason()
+    }
+
     /// Gets called every tick in the update fn to update the internal game logic.
-    /// It updates the player resources, checks on the current milestone if the player has reached a new one
+    /// It updates the player resources, completes the objectives whose conditions are fulfilled
     /// and checks if the player has died.
     /// # Returns
     /// * `RLResult`: A `RLResult` to validate the success of the tick function
@@ -138,38 +256,45 @@ impl GameState {
         self.player.resources = self
             .player
             .resources
-            .into_iter()
-            .zip(self.player.resources_change.into_iter())
-            .map(|(a, b)| a.saturating_add_signed(b))
-            .collect::<Resources<_>>();
+            .saturating_add_signed(self.resources_change());
         self.player.time += 1;
         // Everything inside will only be checked every 15 ticks
 
+        // Empty resources and unmet needs cause status effects which drain the player's life
+        let empty_resource = self.apply_conditions();
+        self.update_rooms();
+        self.update_eva();
+        self.player.update_status_effects();
+
         // Check if the player is dead
-        if let Some(empty_resource) = Resources::get_death_reason(self.player.resources) {
-            match empty_resource {
-                Both => {
-                    self.player.resources_change.life = -60;
-                    self.machines.iter_mut().for_each(Machine::no_energy);
-                }
-                Oxygen => self.player.resources_change.life = -50,
-                Energy => {
-                    self.player.resources_change.life = -10;
-                    self.machines.iter_mut().for_each(Machine::no_energy);
-                }
-            };
-            if self.player.resources.life == 0 {
-                let game_state =
-                    GameState::load(true).unwrap_or_else(|_| GameState::new_with_lang(lng));
-                game_state.save(false)?;
-                let cloned_sender = self.get_screen_sender()?.clone();
-                self.get_screen_sender()?
-                    .send(StackCommand::Screen(ScreenCommand::Push(Box::new(
-                        InfoScreen::new_death_screen(empty_resource, cloned_sender, game_state.lng),
-                    ))))?;
-            };
-        } else if self.player.resources_change.life < 0 {
-            self.player.resources_change.life = 0;
+        if self.player.resources[ResourceKind::Life] == 0 && self.mode == GameMode::Endless {
+            // An endless game is over with the death of the player, there is no checkpoint to return to
+            info!("Endless game over after {} ticks", self.player.time);
+            GameState::delete_saves()?;
+            let time = self.player.time;
+            let cloned_sender = self.get_screen_sender()?.clone();
+            self.get_screen_sender()?
+                .send(StackCommand::Screen(ScreenCommand::Push(Box::new(
+                    InfoScreen::new_death_screen(
+                        empty_resource.unwrap_or(Injury),
+                        cloned_sender,
+                        lng,
+                    )
+                    .with_survival_time(time),
+                ))))?;
+        } else if self.player.resources[ResourceKind::Life] == 0 {
+            let game_state =
+                GameState::load(true).unwrap_or_else(|_| GameState::new_with_lang(lng));
+            game_state.save(false)?;
+            let cloned_sender = self.get_screen_sender()?.clone();
+            self.get_screen_sender()?
+                .send(StackCommand::Screen(ScreenCommand::Push(Box::new(
+                    InfoScreen::new_death_screen(
+                        empty_resource.unwrap_or(Injury),
+                        cloned_sender,
+                        game_state.lng,
+                    ),
+                ))))?;
         }
 
         // process received GameCommands
@@ -179,40 +304,57 @@ impl GameState {
                     self.player.resources_change = self.player.resources_change + new_rs;
                 }
                 GameCommand::AddItems(items) => {
-                    for (item, amount) in &items {
-                        self.player.add_item(item, *amount);
+                    // Items that do not fit into the inventory anymore are lost
+                    let lost = items
+                        .iter()
+                        .map(|(item, amount)| (item, self.player.add_item(item, *amount)))
+                        .filter(|(_, overflow)| *overflow > 0)
+                        .map(|(item, overflow)| format!("*{overflow} {}", item.name))
+                        .collect::<Vec<_>>();
+                    if !lost.is_empty() {
+                        let message =
+                            format!("{}\n{}", trade_conflict_popup(lng)[1], lost.join("\n"));
+                        self.get_screen_sender()?.send(StackCommand::Screen(
+                            ScreenCommand::Popup(Popup::info(message)),
+                        ))?;
                     }
                 }
-                GameCommand::Milestone => {
-                    self.get_current_milestone()?;
+                GameCommand::TradeConcluded(id) => {
+                    let graph = self.objective_graph();
+                    self.objectives.trade_concluded(id, graph);
+                }
+                GameCommand::TriggerEvent(id) => {
+                    self.trigger_event(id)?;
+                }
+                GameCommand::UnlockTrade(id) => {
+                    self.set_trade_locked(id, false);
+                }
+                GameCommand::MitigateEvent(id, percent) => {
+                    Event::mitigate_event(id, percent, self)?;
+                }
+                GameCommand::Heal(amount) => {
+                    let life = &mut self.player.resources[ResourceKind::Life];
+                    *life = life.saturating_add(amount);
+                    self.player.resources = self.player.resources.clamped();
                 }
-                GameCommand::Winning => match self.player.milestone {
-                    1 => {
-                        let sender = self.get_screen_sender()?;
-                        let popup =
-                            Popup::new(RLColor::GREEN, send_msg_failure(lng).to_string(), 5);
-                        sender.send(StackCommand::Screen(ScreenCommand::Popup(popup)))?;
-                    }
-                    2 => {
-                        self.player.milestone += 1;
-                        self.get_current_milestone()?;
-                    }
-                    _ => {}
-                },
             };
         }
 
+        self.update_objectives(false)?;
+        self.update_tutorial();
+
         // Regenerate life if applicable
         self.player
             .life_regeneration(&self.screen_sender.as_ref().unwrap().clone(), lng)?;
         for machine in &mut self.machines {
             machine.tick()?;
         }
+        self.update_breaches();
 
         Ok(())
     }
 
-    /// Paints the current resource level of air, energy and life as a bar on the screen and
+    /// Paints the current resource level of air, energy, life and the needs as a bar on the screen and
     /// draws the amount of every resource in the inventory.
     /// # Arguments
     /// * `canvas`: The canvas to draw on
@@ -221,35 +363,34 @@ impl GameState {
     /// # Returns
     /// * `RLResult`: A `RLResult` to validate the success of the paint function
     fn draw_resources(&self, canvas: &mut Canvas, scale: Vec2, ctx: &mut Context) {
-        self.player
-            .resources
+        ResourceKind::ALL
             .into_iter()
-            .enumerate()
-            .map(|(i, resource)| -> RLResult<()> {
-                let resource = f64::from(resource);
-                let mut color = COLORS[i];
-                if i == 2 && self.player.resources_change.life > 0 {
+            .map(|kind| -> RLResult<()> {
+                let info = kind.info();
+                let fraction = self.player.resources.fraction(kind);
+                let mut color = info.color;
+                if kind == ResourceKind::Life && self.player.resources_change[kind] > 0 {
                     color = RLColor::GREEN;
                 };
-                let rect = Rect::new(
-                    RESOURCE_POSITION[i],
-                    961.0,
-                    (resource * 0.00435_f64) as f32,
-                    12.6,
-                );
+                let (x, y) = info.position;
+                if info.need {
+                    // The needs are drawn outside of the station, so their bars need a background
+                    let track = Rect::new(x, y, RESOURCE_BAR_WIDTH, 12.6);
+                    let mesh = Mesh::new_rounded_rectangle(
+                        ctx,
+                        DrawMode::fill(),
+                        track,
+                        3.0,
+                        RLColor::DARK_GREY,
+                    )?;
+                    draw!(canvas, &mesh, scale);
+                }
+                let rect = Rect::new(x, y, fraction * RESOURCE_BAR_WIDTH, 12.6);
                 let mesh = Mesh::new_rounded_rectangle(ctx, DrawMode::fill(), rect, 3.0, color)?;
                 draw!(canvas, &mesh, scale);
-                let text = graphics::Text::new(format!(
-                    "{}: {:.1}",
-                    resource_name(self.lng)[i],
-                    (resource / f64::from(u16::MAX)) * 100.0
-                ));
-                draw!(
-                    canvas,
-                    &text,
-                    Vec2::new(RESOURCE_POSITION[i] + 20.0, 961.0),
-                    scale
-                );
+                let text =
+                    graphics::Text::new(format!("{}: {:.1}", kind.t(self.lng), fraction * 100.0));
+                draw!(canvas, &text, Vec2::new(x + 20.0, y), scale);
                 Ok(())
             })
             .for_each(drop);
@@ -264,42 +405,33 @@ impl GameState {
         let scale = get_scale(ctx);
         let image = self.assets.get("Handbook.png").unwrap();
         draw!(canvas, image, Vec2::new(700.0, 300.0), scale);
-        match self.player.milestone {
-            1 => {
-                Self::draw_handbook_text(canvas, scale, first_milestone_handbook_text(self.lng));
-            }
-
-            2 => {
-                Self::draw_handbook_text(canvas, scale, second_milestone_handbook_text(self.lng));
-            }
-            _ => {}
-        }
+        let mut text = self
+            .objectives
+            .handbook_text(self.objective_graph(), self.lng);
+        text.push(format!("\n{}", handbook_close(self.lng)));
+        Self::draw_handbook_text(canvas, scale, &text.join("\n"));
     }
-    /// Draws the text for the current milestone on the handbook on the screen.
+    /// Draws the text of the active objectives on the handbook on the screen.
     /// # Arguments
     /// * `canvas`: The canvas to draw on
     /// * `scale`: The scale of the canvas
-    /// * `handbook_text`: The text to draw on the screen
+    /// * `handbook_text`: The text to draw on the screen, every line is drawn separately
     /// # Returns
     /// * `RLResult`: A `RLResult` to validate the success of the function
-    pub fn draw_handbook_text(canvas: &mut Canvas, scale: Vec2, handbook_text: &[&str]) {
-        handbook_text
-            .iter()
-            .enumerate()
-            .for_each(|(i, const_text)| {
-                let mut text =
-                    graphics::Text::new(TextFragment::new(*const_text).color(RLColor::BLACK));
-                text.set_scale(28.0);
-                draw!(
-                    canvas,
-                    &text,
-                    Vec2::new(800.0, 400.0 + (i * 30) as f32),
-                    scale
-                );
-            });
+    pub fn draw_handbook_text(canvas: &mut Canvas, scale: Vec2, handbook_text: &str) {
+        handbook_text.lines().enumerate().for_each(|(i, line)| {
+            let mut text = graphics::Text::new(TextFragment::new(line).color(RLColor::BLACK));
+            text.set_scale(28.0);
+            draw!(
+                canvas,
+                &text,
+                Vec2::new(800.0, 400.0 + (i * 30) as f32),
+                scale
+            );
+        });
     }
 
-    /// Iterates trough the inventory and draws the amount of every item in the inventory.
+    /// Iterates trough the inventory and draws the amount of every item in the inventory and the carry weight.
     /// # Arguments
     /// * `canvas` - The current canvas to draw on
     /// * `ctx` - The current game context
@@ -329,21 +461,25 @@ impl GameState {
                 );
             })
             .for_each(drop);
+        let weight = graphics::Text::new(format!(
+            "{}: {}/{CARRY_CAPACITY}",
+            carry_weight(self.lng),
+            self.player.carry_weight()
+        ));
+        draw!(canvas, &weight, Vec2::new(990., 995.), get_scale(ctx));
     }
 
-    /// Draws the current time on the screen
+    /// Draws the current time on the screen, in the endless mode it is the score of the player
     /// # Arguments
     /// * `canvas` - The current canvas to draw on
     /// * `scale` - The current scale of the canvas
     pub(crate) fn draw_time(&self, canvas: &mut Canvas, scale: Vec2) {
         let time = self.player.time / DESIRED_FPS;
-        let time_text = format!(
-            "{}: {}h {}m {}s",
-            time_name(self.lng)[0],
-            time / 3600,
-            time / 60,
-            time % 60
-        );
+        let label = match self.mode {
+            GameMode::Story => time_name(self.lng)[0],
+            GameMode::Endless => survival_time(self.lng)[0],
+        };
+        let time_text = format!("{}: {}h {}m {}s", label, time / 3600, time / 60, time % 60);
         let mut text = graphics::Text::new(TextFragment::new(time_text).color(RLColor::BLACK));
         text.set_scale(18.0);
         draw!(
@@ -353,6 +489,30 @@ impl GameState {
             scale
         );
     }
+    /// Draws an icon with the name of every status effect the player suffers from, stacks are shown next to the icon
+    /// # Arguments
+    /// * `canvas`: The canvas to draw on
+    /// * `scale`: The scale of the canvas
+    /// # Returns
+    /// * `RLResult`: A `RLResult` to validate the success of the paint function
+    fn draw_status_effects(&self, canvas: &mut Canvas, scale: Vec2) -> RLResult {
+        for (i, effect) in self.player.status_effects.iter().enumerate() {
+            let position = Vec2::new(STATUS_POSITION.0 + 80. * i as f32, STATUS_POSITION.1);
+            let icon = self.get_asset(&format!("status_{:?}.png", effect.kind))?;
+            draw!(canvas, icon, position, scale);
+            let mut name = graphics::Text::new(TextFragment::new(effect.kind.t(self.lng)));
+            name.set_scale(12.);
+            draw!(canvas, &name, position + Vec2::new(0., 44.), scale);
+            if effect.stacks > 1 {
+                let mut stacks =
+                    graphics::Text::new(TextFragment::new(format!("{}", effect.stacks)));
+                stacks.set_scale(16.);
+                draw!(canvas, &stacks, position + Vec2::new(30., 28.), scale);
+            }
+        }
+        Ok(())
+    }
+
     /// Loads the assets. Has to be called before drawing the game.
     /// # Returns
     /// * `RLResult` - Returns an error if the assets could not be loaded.
@@ -378,46 +538,71 @@ impl GameState {
         Ok(())
     }
     /// Initializes the machines by loading the assets for all existing machines
-    /// Checks if the machine has one asset if it does not change or three assets for the different states
     pub(crate) fn init_all_machines(&mut self) {
-        let machine_assets: Vec<Vec<Image>> = self
+        let machine_assets: Vec<Vec<Vec<Image>>> = self
             .machines
             .iter()
-            .map(|m| m.id.t(Lang::De))
-            .map(|name| {
-                info!("Loading assets for {}", name);
-                if self.assets.contains_key(&format!("{name}.png")) {
-                    vec![self.assets.get(&format!("{name}.png")).unwrap().clone()]
-                } else {
-                    vec![
-                        self.assets
-                            .get(&format!("{name}_Broken.png"))
-                            .unwrap()
-                            .clone(),
-                        self.assets
-                            .get(&format!("{name}_Idle.png"))
-                            .unwrap()
-                            .clone(),
-                        self.assets
-                            .get(&format!("{name}_Running.png"))
-                            .unwrap()
-                            .clone(),
-                    ]
-                }
-            })
+            .map(|m| self.get_machine_sprites(m))
             .collect();
         self.machines
             .iter_mut()
             .zip(machine_assets)
             .for_each(|(m, a)| {
                 m.init(
-                    a.as_slice(),
+                    a,
                     self.sender.clone().unwrap(),
                     self.screen_sender.clone().unwrap(),
                 );
             });
     }
 
+    /// Initializes a single machine which is added while the game is running.
+    /// Machines added before the game is on the screen stack are initialized by `init_all_machines`.
+    /// # Arguments
+    /// * `machine` - The machine to initialize
+    pub(crate) fn init_machine(&self, machine: &mut Machine) {
+        if let (Some(sender), Some(screen_sender)) = (&self.sender, &self.screen_sender) {
+            machine.init(
+                self.get_machine_sprites(machine),
+                sender.clone(),
+                screen_sender.clone(),
+            );
+        }
+    }
+
+    /// Loads the sprites of a machine.
+    /// Checks if the machine has one asset if it does not change or three assets for the different states.
+    /// Upgraded tiers can have their own three assets named `{name}_Tier{tier}_{state}.png`
+    /// # Arguments
+    /// * `machine` - The machine to load the sprites for
+    /// # Returns
+    /// * `Vec<Vec<Image>>` - The sprites of every tier that has its own sprites
+    fn get_machine_sprites(&self, machine: &Machine) -> Vec<Vec<Image>> {
+        let name = machine.id.t(Lang::De);
+        info!("Loading assets for {}", name);
+        if let Some(image) = self.assets.get(&format!("{name}.png")) {
+            return vec![vec![image.clone()]];
+        }
+        let mut tiers = vec![self.get_state_sprites(name).unwrap()];
+        tiers.extend(
+            (2..=machine.max_tier())
+                .map_while(|tier| self.get_state_sprites(&format!("{name}_Tier{tier}"))),
+        );
+        tiers
+    }
+
+    /// Returns the sprites of the states broken, idle and running for the given asset name
+    /// # Arguments
+    /// * `name` - The name of the asset group
+    /// # Returns
+    /// * `Option<Vec<Image>>` - The three sprites or None if one of them does not exist
+    fn get_state_sprites(&self, name: &str) -> Option<Vec<Image>> {
+        ["Broken", "Idle", "Running"]
+            .iter()
+            .map(|state| self.assets.get(&format!("{name}_{state}.png")).cloned())
+            .collect()
+    }
+
     /// Saves the active game state to a file. The boolean value "milestone" determines whether this is a milestone or an autosave.
     /// If the file already exists, it will be overwritten.
     /// # Arguments
@@ -462,39 +647,118 @@ impl GameState {
             info!("Loading autosave...");
             fs::read_to_string(root.as_ref().join("saves/autosave.yaml"))
         }?;
-        let game_state: GameState = serde_yaml::from_str(&save_data)?;
+        let mut game_state: GameState = serde_yaml::from_str(&save_data)?;
+        // older saves subtracted the resource change of active events from the player
+        for event in &mut game_state.events {
+            if let Some(resources) = event.migrate_legacy_resources() {
+                game_state.player.resources_change = game_state.player.resources_change + resources;
+            }
+        }
+        game_state.player.add_missing_items(game_state.lng);
+        game_state.update_collision();
 
         Ok(game_state)
     }
 
-    /// Returns the area the player needs to stand in to interact with a machine
+    /// Rebuilds the spatial indices of the obstacles and the interactables.
+    /// Has to be called whenever machines are added or removed or a door is opened or closed.
+    pub(crate) fn update_collision(&mut self) {
+        let map = self.map();
+        self.obstacles = self
+            .machines
+            .iter()
+            .map(|machine| Shape::from(machine.hitbox))
+            .chain(self.closed_doors().map(|door| Shape::from(door.area)))
+            .chain(map.rock_shapes())
+            .map(|shape| (shape, ()))
+            .collect();
+
+        let machines = self.machines.iter().enumerate().map(|(index, machine)| {
+            (
+                Shape::from(machine.interaction_area),
+                (Interactable::Machine(index), Shape::from(machine.hitbox)),
+            )
+        });
+        let points = map
+            .scavenge_points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                (
+                    point.interaction_shape(),
+                    (Interactable::ScavengePoint(index), point.shape()),
+                )
+            });
+        let doors = map.doors.iter().enumerate().map(|(index, door)| {
+            let area = Rect::new(
+                door.area.x - PLAYER_INTERACTION_RADIUS,
+                door.area.y - PLAYER_INTERACTION_RADIUS,
+                door.area.w + PLAYER_INTERACTION_RADIUS * 2.,
+                door.area.h + PLAYER_INTERACTION_RADIUS * 2.,
+            );
+            (
+                Shape::from(area),
+                (Interactable::Door(index), Shape::from(door.area)),
+            )
+        });
+        self.interactables = machines.chain(points).chain(doors).collect();
+    }
+
+    /// Returns whether the player is standing in the interaction area of the given interactable
+    /// # Arguments
+    /// * `interactable` - The machine, scavenge point or door
+    pub(crate) fn is_in_reach(&self, interactable: Interactable) -> bool {
+        let player = Shape::from(Self::player_area(self.player.position));
+        self.interactables
+            .query(&player)
+            .any(|(_, (other, _))| *other == interactable)
+    }
+
+    /// Returns the machine, scavenge point or door the player interacts with when pressing E.
+    /// If the player stands in several interaction areas the nearest one in front of them is chosen.
     /// # Returns
-    /// * `Option<&mut Machine>` - The machines the player can interact with if one exists or None
-    pub(crate) fn get_interactable(&mut self) -> Option<&mut Machine> {
+    /// * `Option<Interactable>` - The machine, scavenge point or door the player can interact with if one exists or None
+    pub(crate) fn get_interactable(&self) -> Option<Interactable> {
+        let player = Shape::from(Self::player_area(self.player.position));
+        nearest_facing(
+            player.center(),
+            Vec2::from(self.player.facing),
+            self.interactables
+                .query(&player)
+                .map(|(_, (interactable, shape))| (shape.clone(), *interactable)),
+        )
+    }
+
+    /// Locks or unlocks every trade with the given id
+    /// # Arguments
+    /// * `id` - The id of the trades
+    /// * `locked` - Whether the trades should be locked
+    pub(crate) fn set_trade_locked(&mut self, id: TradeId, locked: bool) {
+        info!("Setting trade {:?} to locked: {}", id, locked);
         self.machines
             .iter_mut()
-            .find(|machine| machine.is_interactable(self.player.position))
+            .flat_map(|machine| machine.trades.iter_mut())
+            .filter(|trade| trade.id == id)
+            .for_each(|trade| trade.locked = locked);
     }
 
-    /// Returns if the player would collide with a border if they moved in the given direction
+    /// Returns whether a new machine can be placed in the given area.
+    /// The area has to be on the floor of the map and must neither overlap a machine nor the player.
     /// # Arguments
-    /// * `next_player_pos` - The direction the player wants to move
-    fn border_collision_detection(next_player_pos: (usize, usize)) -> bool {
-        next_player_pos.0 >= MAP_BORDER[0] // Right border
-            || next_player_pos.1 >= MAP_BORDER[1] // Bottom border
-            || next_player_pos.0 <= MAP_BORDER[2] // Left border
-            || next_player_pos.1 <= MAP_BORDER[3] // Top border
-    }
-    /// Returns a boolean indicating whether the player would collide with a machine or border if they moved in the given direction
+    /// * `area` - The hitbox of the new machine
+    pub(crate) fn is_valid_placement(&self, area: &Rect) -> bool {
+        self.map().is_buildable(area)
+            && !self.machines.iter().any(|m| m.hitbox.overlaps(area))
+            && !is_colliding(self.player.position, area)
+    }
+
+    /// Returns a boolean indicating whether the player would collide with an obstacle or leave the walkable tiles of the map if they moved in the given direction
     ///
     /// # Arguments
     /// * `next_player_pos` - A tuple containing the next position of the player
-    pub(crate) fn collision_detection(&self, next_player_pos: (usize, usize)) -> bool {
-        self.machines
-            .iter()
-            .map(|area| area.hitbox)
-            .any(|area| is_colliding(next_player_pos, &area))
-            || Self::border_collision_detection(next_player_pos)
+    pub(crate) fn collision_detection(&self, next_player_pos: (f32, f32)) -> bool {
+        let area = Self::player_area(next_player_pos);
+        self.obstacles.overlaps(&Shape::from(area)) || !self.map().is_walkable(&area)
     }
     /// Returns the asset if it exists
     /// # Arguments
@@ -506,65 +770,6 @@ impl GameState {
             "Could not find asset with name {name}"
         )))
     }
-    /// Checks if the milestone is reached which means the vec of repaired machines
-    /// contain the vec of machines needed to reach the next milestone.
-    /// # Arguments
-    /// * `milestone_machines` - A vec of machines needed to reach the next milestone
-    pub fn check_on_milestone_machines(&mut self, milestone_machines: &[ObjectId]) -> bool {
-        let running_machine = self
-            .machines
-            .iter()
-            .filter(|m| m.state != Broken)
-            .map(|m| m.id)
-            .collect::<Vec<_>>();
-
-        if milestone_machines
-            .iter()
-            .all(|machine| running_machine.contains(machine))
-        {
-            return true;
-        }
-        false
-    }
-    fn increase_milestone(&mut self) -> RLResult {
-        self.player.milestone += 1;
-        info!("Player reached milestone {}", self.player.milestone);
-        self.save(true)?;
-        Ok(())
-    }
-    /// Decides what happens if a certain milestone is reached
-    /// divided into 3 milestones
-    fn get_current_milestone(&mut self) -> RLResult {
-        let lng = self.lang();
-
-        match self.player.milestone {
-            0 => {
-                self.player.resources_change.oxygen = -1;
-                self.player.resources_change.energy = -1;
-                self.player.last_damage = 0;
-                self.increase_milestone()?;
-            }
-            1 => {
-                if self.check_on_milestone_machines(&[
-                    ObjectId::OxygenGenerator,
-                    ObjectId::PowerGenerator,
-                ]) {
-                    self.increase_milestone()?;
-                }
-            }
-            3 => {
-                info!("Player won the Game");
-                self.player.milestone += 1;
-                let cloned_sender = self.get_screen_sender()?.clone();
-                self.get_screen_sender()?
-                    .send(StackCommand::Screen(ScreenCommand::Push(Box::new(
-                        InfoScreen::new_winning_screen(cloned_sender, lng),
-                    ))))?;
-            }
-            _ => {}
-        }
-        Ok(())
-    }
     /// Deletes all files in the directory saves, returns Ok if saves directory does not exist
     pub(crate) fn delete_saves() -> RLResult {
         info!("deleting saves");
@@ -588,8 +793,10 @@ impl Screen for GameState {
         if ctx.time.check_update_time(DESIRED_FPS) {
             self.tick()?;
             self.move_player(ctx)?;
-            Event::update_events(ctx, self)?;
+            Event::update_events(self)?;
         }
+        // The player moves every frame, their speed does not depend on the update rate
+        self.update_movement(Self::movement_input(ctx), ctx.time.delta().as_secs_f32());
         Ok(())
     }
     /// Draws the game state to the screen.
@@ -600,18 +807,23 @@ impl Screen for GameState {
     fn draw(&self, ctx: &mut Context) -> RLResult {
         let scale = get_scale(ctx);
         let mut canvas = Canvas::from_frame(ctx, graphics::Color::from([0.1, 0.2, 0.3, 1.0]));
-        let background = self.get_asset("basis.png")?;
-        canvas.draw(background, graphics::DrawParam::default().scale(scale));
+        self.draw_map(&mut canvas, scale, ctx)?;
+        self.draw_rooms(&mut canvas, scale, ctx)?;
+        self.draw_eva(&mut canvas, scale, ctx)?;
         let player = self.get_asset("player.png")?;
-        draw!(
-            canvas,
-            player,
-            Vec2::from([self.player.position.0 as f32, self.player.position.1 as f32]),
-            scale
-        );
+        draw!(canvas, player, Vec2::from(self.player.position), scale);
         self.draw_resources(&mut canvas, scale, ctx);
         self.draw_machines(&mut canvas, scale, ctx)?;
+        self.draw_tutorial(&mut canvas, scale, ctx)?;
         self.draw_items(&mut canvas, ctx);
+        if let Some(build_mode) = &self.build_mode {
+            build_mode.draw(self, &mut canvas, scale, ctx)?;
+        }
+        if let Some(panel) = &self.trade_panel {
+            if let Some(machine) = self.machines.get(panel.machine) {
+                panel.draw(machine, &self.player, &mut canvas, scale, ctx, self.lng)?;
+            }
+        }
         if !self.handbook_invisible {
             self.open_handbook(&mut canvas, ctx);
         }
@@ -619,8 +831,15 @@ impl Screen for GameState {
         {
             let fps = graphics::Text::new(format!("FPS: {}", ctx.time.fps()));
             draw!(canvas, &fps, Vec2::new(1400.0, 0.0), scale);
-            let milestone = graphics::Text::new(format!("Milestone: {}", self.player.milestone));
-            draw!(canvas, &milestone, Vec2::new(1400.0, 20.0), scale);
+            let objectives = graphics::Text::new(format!(
+                "Objectives: {:?}",
+                self.objectives
+                    .active
+                    .iter()
+                    .map(|objective| &objective.id)
+                    .collect::<Vec<_>>()
+            ));
+            draw!(canvas, &objectives, Vec2::new(1400.0, 20.0), scale);
             let events = graphics::Text::new(format!("Events: {:?}", self.events));
             draw!(canvas, &events, Vec2::new(1400.0, 40.0), scale);
             let last_damage =
@@ -628,19 +847,24 @@ impl Screen for GameState {
             draw!(canvas, &last_damage, Vec2::new(1400.0, 60.0), scale);
             let oxygen_cr = graphics::Text::new(format!(
                 "Oxygen CR: {}",
-                self.player.resources_change.oxygen
+                self.resources_change()[ResourceKind::Oxygen]
             ));
             draw!(canvas, &oxygen_cr, Vec2::new(1400.0, 80.0), scale);
             let energy_cr = graphics::Text::new(format!(
                 "Energy CR: {}",
-                self.player.resources_change.energy
+                self.resources_change()[ResourceKind::Energy]
             ));
             draw!(canvas, &energy_cr, Vec2::new(1400.0, 100.0), scale);
-            let life_cr =
-                graphics::Text::new(format!("Life CR: {}", self.player.resources_change.life));
+            let life_cr = graphics::Text::new(format!(
+                "Life CR: {}",
+                self.player.resources_change[ResourceKind::Life]
+            ));
             draw!(canvas, &life_cr, Vec2::new(1400.0, 120.0), scale);
         }
         self.draw_time(&mut canvas, scale);
+        self.draw_forecast(&mut canvas, scale);
+        self.draw_objective_tracker(&mut canvas, scale);
+        self.draw_status_effects(&mut canvas, scale)?;
         canvas.finish(ctx)?;
         Ok(())
     }
@@ -660,6 +884,7 @@ impl Screen for GameState {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::game_core::event::EventEffect;
 
     #[test]
     fn test_gamestate() {
@@ -710,6 +935,130 @@ mod test {
         let _gamestate_loaded = GameState::load_from_dir(true, tmp.to_path_buf()).unwrap();
     }
 
+    #[test]
+    fn test_load_legacy_event() {
+        // Older saves subtracted the resource change of an active event from the player
+        let gamestate = GameState::load_from_dir(false, "test-saves/legacy_event").unwrap();
+        assert_eq!(gamestate.player.resources_change[ResourceKind::Oxygen], -5);
+        assert_eq!(gamestate.resources_change()[ResourceKind::Oxygen], -15);
+        assert!(gamestate.events[0]
+            .effects
+            .iter()
+            .any(|effect| matches!(effect, EventEffect::ResourceChange(change) if change[ResourceKind::Oxygen] == -10)));
+    }
+
+    #[test]
+    fn test_placement_validation() {
+        let mut gamestate = GameState::new_with_lang(Lang::De);
+        gamestate.start_scenario(&default_scenario_id());
+        // Free floor next to the player
+        assert!(gamestate.is_valid_placement(&Rect::new(700., 500., 90., 70.)));
+        // Overlaps the oxygen generator
+        assert!(!gamestate.is_valid_placement(&Rect::new(300., 250., 90., 70.)));
+        // Outside of the map border
+        assert!(!gamestate.is_valid_placement(&Rect::new(100., 500., 90., 70.)));
+        // Overlaps the player
+        assert!(!gamestate.is_valid_placement(&Rect::new(590., 490., 90., 70.)));
+    }
+
+    #[test]
+    fn test_overlapping_interactables() {
+        let mut gamestate = GameState::new_with_lang(Lang::De);
+        gamestate.start_scenario(&default_scenario_id());
+        gamestate.machines.truncate(2);
+        gamestate.player.position = (400., 420.);
+        // Two machines left and right of the player which share their interaction area
+        for (machine, x) in gamestate.machines.iter_mut().zip([300., 500.]) {
+            machine.hitbox = Rect::new(x, 440., 60., 60.);
+            machine.interaction_area = Rect::new(280., 400., 300., 150.);
+        }
+        gamestate.update_collision();
+        gamestate.player.facing = (1., 0.);
+        assert_eq!(gamestate.get_interactable(), Some(Interactable::Machine(1)));
+        gamestate.player.facing = (-1., 0.);
+        assert_eq!(gamestate.get_interactable(), Some(Interactable::Machine(0)));
+        assert!(gamestate.is_in_reach(Interactable::Machine(1)));
+    }
+
+    /// Creates a gamestate with a screen sender, so events can send their popups
+    fn gamestate_with_screen() -> (GameState, Receiver<StackCommand>) {
+        let mut gamestate = GameState::new_with_lang(Lang::En);
+        let (sender, receiver) = channel();
+        gamestate.screen_sender = Some(sender);
+        (gamestate, receiver)
+    }
+
+    #[test]
+    fn test_event_rate_returns_to_baseline() {
+        let (mut gamestate, _receiver) = gamestate_with_screen();
+        let baseline = gamestate.resources_change();
+        gamestate.trigger_event(EventId::Sandstorm).unwrap();
+        assert_ne!(gamestate.resources_change(), baseline);
+        for _ in 0..10_000 {
+            if gamestate.events.is_empty() {
+                break;
+            }
+            Event::update_events(&mut gamestate).unwrap();
+        }
+        assert!(gamestate.events.is_empty());
+        assert_eq!(gamestate.resources_change(), baseline);
+    }
+
+    #[test]
+    fn test_event_rate_after_loading() {
+        let (mut gamestate, _receiver) = gamestate_with_screen();
+        let baseline = gamestate.resources_change();
+        gamestate.trigger_event(EventId::Sandstorm).unwrap();
+        let during_event = gamestate.resources_change();
+        let save_data = serde_yaml::to_string(&gamestate).unwrap();
+        let mut loaded: GameState = serde_yaml::from_str(&save_data).unwrap();
+        assert_eq!(loaded.resources_change(), during_event);
+        // removing the event in any way restores the baseline
+        loaded.events.clear();
+        assert_eq!(loaded.resources_change(), baseline);
+    }
+
+    #[test]
+    fn test_mitigated_event_rate() {
+        let (mut gamestate, _receiver) = gamestate_with_screen();
+        let baseline = gamestate.resources_change();
+        gamestate.trigger_event(EventId::Sandstorm).unwrap();
+        let full_change = gamestate.resources_change() - baseline;
+        Event::mitigate_event(EventId::Sandstorm, 50, &mut gamestate).unwrap();
+        assert_eq!(
+            gamestate.resources_change() - baseline,
+            full_change.into_iter().map(|change| change / 2).collect()
+        );
+        // the mitigation is relative to the current strength, so only 100% ends the event
+        Event::mitigate_event(EventId::Sandstorm, 100, &mut gamestate).unwrap();
+        assert_eq!(gamestate.resources_change(), baseline);
+        Event::update_events(&mut gamestate).unwrap();
+        assert!(gamestate.events.is_empty());
+        assert_eq!(gamestate.resources_change(), baseline);
+    }
+
+    #[test]
+    fn test_mitigated_escalated_event() {
+        let (mut gamestate, _receiver) = gamestate_with_screen();
+        let baseline = gamestate.resources_change();
+        gamestate.trigger_event(EventId::PowerFailure).unwrap();
+        let full_change = gamestate.resources_change() - baseline;
+        Event::mitigate_event(EventId::PowerFailure, 100, &mut gamestate).unwrap();
+        gamestate.events.clear();
+
+        // An event at twice its strength is halved by a mitigation of 50%
+        let mut event = Event::from_id(EventId::PowerFailure, gamestate.event_table(), Lang::En);
+        event.action(200, &mut gamestate).unwrap();
+        gamestate.events.push(event);
+        Event::mitigate_event(EventId::PowerFailure, 50, &mut gamestate).unwrap();
+        assert_eq!(gamestate.events[0].strength, 100);
+        assert_eq!(gamestate.resources_change() - baseline, full_change);
+        // A mitigation of 100% ends it
+        Event::mitigate_event(EventId::PowerFailure, 100, &mut gamestate).unwrap();
+        assert_eq!(gamestate.events[0].strength, 0);
+        assert_eq!(gamestate.resources_change(), baseline);
+    }
+
     #[test]
     fn test_delete_saves() {
         GameState::delete_saves().unwrap();
@@ -1,5 +1,4 @@
 //! This File handels code surrounding Machine with in `GameState`
-use crate::backend::constants::gen_all_machines;
 use crate::backend::gamestate::GameState;
 use crate::backend::rlcolor::RLColor;
 use crate::backend::utils::get_draw_params;
@@ -7,15 +6,8 @@ use crate::{draw, RLResult};
 use ggez::glam::Vec2;
 use ggez::graphics::{Canvas, Mesh, Rect};
 use ggez::Context;
-use tracing::info;
 
 impl GameState {
-    /// Creates all Machines for initial creation and pushes them into a list
-    pub fn create_machine(&mut self) {
-        info!("Generating all Machines");
-        self.machines = gen_all_machines(self.lng);
-    }
-
     /// Paints the machine sprites and if applicable it shows the state or time remaining
     /// # Arguments
     /// * `canvas`: The canvas to draw on
@@ -30,7 +22,7 @@ impl GameState {
                 x: machine.hitbox.x,
                 y: machine.hitbox.y,
             };
-            draw!(canvas, image, pos, scale);
+            draw!(canvas, image, Some(pos), scale, machine.get_tint());
             if !machine.id.is_hole() {
                 // Draws the machine status on top of the machine
                 let status = Mesh::new_circle(
@@ -44,7 +36,19 @@ impl GameState {
                 pos.x += 20.;
                 pos.y += 20.;
                 draw!(canvas, &status, pos, scale);
-            };
+            } else if let Some(breach) = machine.breach {
+                // Draws one red mark per severity step below the breach
+                for step in 0..breach.severity {
+                    let mark = Mesh::new_rectangle(
+                        ctx,
+                        ggez::graphics::DrawMode::fill(),
+                        Rect::new(0., 0., 8., 8.),
+                        RLColor::STATUS_RED,
+                    )?;
+                    let offset = Vec2::new(f32::from(step) * 12., machine.hitbox.h + 4.);
+                    draw!(canvas, &mark, pos + offset, scale);
+                }
+            }
             // Draws the machine timer on top of the machine
             let time = machine.get_time_percentage();
             if time > 0. {
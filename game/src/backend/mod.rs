@@ -1,3 +1,4 @@
+pub(crate) mod collision;
 pub(crate) mod constants;
 pub(crate) mod error;
 pub(crate) mod gamestate;
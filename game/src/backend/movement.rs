@@ -1,86 +1,263 @@
 //! This file contains the movement system, which is responsible for moving the player around the map and to interact with objects.
-use crate::backend::constants::MOVEMENT_SPEED;
-use crate::backend::gamestate::GameState;
+use crate::backend::constants::{
+    gen_blueprints, MAX_MOVEMENT_DELTA, PLAYER_ACCELERATION, PLAYER_DECELERATION, PLAYER_STEP,
+};
+use crate::backend::gamestate::{GameState, Interactable};
 use crate::backend::screen::{Screen, ScreenCommand, StackCommand};
+use crate::game_core::inventory_screen::InventoryScreen;
+use crate::machines::build_mode::BuildMode;
+use crate::machines::trade_panel::TradePanel;
 use crate::RLResult;
+use ggez::glam::Vec2;
 use ggez::winit::event::VirtualKeyCode;
 use ggez::Context;
 use tracing::info;
 
+/// Contains how often the distance to an obstacle is halved to move the player right next to it
+const CONTACT_ITERATIONS: usize = 8;
+
 impl GameState {
-    /// Handles the player movement and updates the player position
-    /// Checks on every move if the next step is inside the borders of the map if not it will not move
+    /// Handles the input of the player, the movement itself is handled by `update_movement`
     /// Handles escape which will pause the game and go to the main menu
+    /// Handles E which opens the trades of a machine or opens and closes a door
+    /// Handles B which toggles the build mode
+    /// Handles I which opens the inventory
+    /// Handles O which collapses or expands the objective tracker
     ///  # Arguments
     /// * `ctx` - The game context which is needed to get the pressed keys
     /// # Returns
     /// * `RLResult<()>` - Returns okay, if no Error occurred
     pub fn move_player(&mut self, ctx: &mut Context) -> RLResult {
-        let lng = self.lang();
+        // While the trade panel is open the player stands still and the input is used for the panel
+        if self.trade_panel.is_some() {
+            return self.handle_trade_panel(ctx);
+        }
         if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Escape) {
             info!("Exiting...");
             self.save(false)?;
             self.get_screen_sender()?
                 .send(StackCommand::Screen(ScreenCommand::Pop))?;
         }
-        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::E) {
-            info!("Interacting with Area: {:?}", self.get_interactable());
-            let player_ref = &self.player.clone();
-            if let Some(interactable) = self.get_interactable() {
-                interactable.interact(player_ref, lng)?;
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::B) {
+            self.build_mode = match self.build_mode {
+                Some(_) => None,
+                None => Some(BuildMode::default()),
+            };
+        }
+        if let Some(build_mode) = self.build_mode.as_mut() {
+            if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Tab) {
+                build_mode.select_next(gen_blueprints(self.lng).len());
+            }
+            if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::E) {
+                self.build_machine()?;
+            }
+        } else if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::E) {
+            match self.get_interactable() {
+                Some(Interactable::Machine(machine)) => {
+                    info!("Opening trade panel of: {:?}", self.machines[machine].id);
+                    self.trade_panel = Some(TradePanel::new(machine));
+                }
+                Some(Interactable::Door(door)) => self.toggle_door(door),
+                Some(Interactable::ScavengePoint(point)) => self.scavenge(point)?,
+                None => {}
             }
         }
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::I) {
+            let sender = self.get_screen_sender()?.clone();
+            sender.send(StackCommand::Screen(ScreenCommand::Push(Box::new(
+                InventoryScreen::new(
+                    &self.player.inventory,
+                    &self.assets,
+                    sender.clone(),
+                    self.lng,
+                ),
+            ))))?;
+        }
         if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::H) {
             self.handbook_invisible = !self.handbook_invisible;
         }
-        // If we are in debug mode, change the milestone by using Z
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::O) {
+            self.tracker_collapsed = !self.tracker_collapsed;
+        }
+        // If we are in debug mode, complete the active objectives by using Z
         #[cfg(debug_assertions)]
         if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Z) {
-            self.player.milestone += 1;
+            self.update_objectives(true)?;
         }
+        Ok(())
+    }
+
+    /// Returns the direction the player wants to move in from the pressed WASD keys.
+    /// The direction has a length of one, so moving diagonally is not faster than moving straight.
+    /// # Arguments
+    /// * `ctx` - The game context which is needed to get the pressed keys
+    pub(crate) fn movement_input(ctx: &Context) -> Vec2 {
         let keys = ctx.keyboard.pressed_keys();
-        for key in keys.iter() {
-            match key {
-                VirtualKeyCode::W => {
-                    if !self.collision_detection((
-                        self.player.position.0,
-                        self.player.position.1.saturating_sub(MOVEMENT_SPEED),
-                    )) {
-                        self.player.position.1 =
-                            self.player.position.1.saturating_sub(MOVEMENT_SPEED);
-                    }
-                }
-                VirtualKeyCode::A => {
-                    if !self.collision_detection((
-                        self.player.position.0.saturating_sub(MOVEMENT_SPEED),
-                        self.player.position.1,
-                    )) {
-                        self.player.position.0 =
-                            self.player.position.0.saturating_sub(MOVEMENT_SPEED);
-                    }
-                }
-                VirtualKeyCode::S => {
-                    if !self.collision_detection((
-                        self.player.position.0,
-                        self.player.position.1.saturating_add(MOVEMENT_SPEED),
-                    )) {
-                        self.player.position.1 =
-                            self.player.position.1.saturating_add(MOVEMENT_SPEED);
-                    }
-                }
-                VirtualKeyCode::D => {
-                    if !self.collision_detection((
-                        self.player.position.0.saturating_add(MOVEMENT_SPEED),
-                        self.player.position.1,
-                    )) {
-                        self.player.position.0 =
-                            self.player.position.0.saturating_add(MOVEMENT_SPEED);
-                    }
+        let axis = |negative, positive| {
+            f32::from(u8::from(keys.contains(&positive)))
+                - f32::from(u8::from(keys.contains(&negative)))
+        };
+        Vec2::new(
+            axis(VirtualKeyCode::A, VirtualKeyCode::D),
+            axis(VirtualKeyCode::W, VirtualKeyCode::S),
+        )
+        .normalize_or_zero()
+    }
+
+    /// Speeds the player up towards the given direction, or slows them down if there is none, and moves them.
+    /// While the trade panel is open the player stands still.
+    /// # Arguments
+    /// * `direction` - The direction the player wants to move in, it has a length of one or zero
+    /// * `delta` - The time since the last update in seconds
+    pub(crate) fn update_movement(&mut self, direction: Vec2, delta: f32) {
+        let delta = delta.min(MAX_MOVEMENT_DELTA);
+        let direction = if self.trade_panel.is_some() {
+            Vec2::ZERO
+        } else {
+            direction
+        };
+        let target = direction * self.player.movement_speed();
+        let rate = if direction == Vec2::ZERO {
+            PLAYER_DECELERATION
+        } else {
+            PLAYER_ACCELERATION
+        };
+        if direction != Vec2::ZERO {
+            self.player.facing = direction.into();
+        }
+        let velocity = Vec2::from(self.player.velocity);
+        let velocity = velocity + (target - velocity).clamp_length_max(rate * delta);
+        self.player.velocity = velocity.into();
+
+        // The player moves in short steps, so they can not pass through walls at high speeds
+        let distance = velocity * delta;
+        let steps = (distance.length() / PLAYER_STEP).ceil().max(1.);
+        #[allow(clippy::cast_sign_loss)]
+        for _ in 0..steps as u32 {
+            self.step_player(distance / steps);
+        }
+    }
+
+    /// Moves the player by the given distance, the horizontal and vertical movement are checked separately.
+    /// If the player runs into something they stop at it and keep sliding along it.
+    /// # Arguments
+    /// * `distance` - The distance the player moves in px
+    fn step_player(&mut self, distance: Vec2) {
+        for axis in [Vec2::X, Vec2::Y] {
+            let movement = distance * axis;
+            if movement == Vec2::ZERO {
+                continue;
+            }
+            let position = Vec2::from(self.player.position);
+            if !self.collision_detection((position + movement).into()) {
+                self.player.position = (position + movement).into();
+                continue;
+            }
+            // Move as close to the obstacle as possible
+            let (mut free, mut blocked) = (0., 1.);
+            for _ in 0..CONTACT_ITERATIONS {
+                let middle = f32::midpoint(free, blocked);
+                if self.collision_detection((position + movement * middle).into()) {
+                    blocked = middle;
+                } else {
+                    free = middle;
                 }
-                _ => {}
             }
+            self.player.position = (position + movement * free).into();
+            // The player stops moving towards the obstacle
+            self.player.velocity = (Vec2::from(self.player.velocity) * axis.perp().abs()).into();
         }
+    }
 
+    /// Handles the input while the trade panel is open.
+    /// W and S change the selected trade, E executes it and Q or Escape close the panel.
+    /// The panel is closed as well if the player is no longer able to interact with the machine.
+    ///  # Arguments
+    /// * `ctx` - The game context which is needed to get the pressed keys
+    /// # Returns
+    /// * `RLResult<()>` - Returns okay, if no Error occurred
+    fn handle_trade_panel(&mut self, ctx: &mut Context) -> RLResult {
+        let lng = self.lang();
+        let Some(mut panel) = self.trade_panel else {
+            return Ok(());
+        };
+        if !self.is_in_reach(Interactable::Machine(panel.machine))
+            || ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Q)
+            || ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Escape)
+        {
+            self.trade_panel = None;
+            return Ok(());
+        }
+        let trade_count = self.machines[panel.machine].listed_trades().len();
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::W)
+            || ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Up)
+        {
+            panel.select_previous(trade_count);
+        }
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::S)
+            || ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Down)
+        {
+            panel.select_next(trade_count);
+        }
+        self.trade_panel = Some(panel);
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::E)
+            || ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Return)
+        {
+            let player = self.player.clone();
+            let machine = &mut self.machines[panel.machine];
+            if let Some(trade) = machine
+                .listed_trades()
+                .get(panel.selected)
+                .map(|&trade| trade.clone())
+            {
+                if machine.is_trade_available(&trade)
+                    && player.missing_items(&trade.cost).is_empty()
+                {
+                    self.trade_panel = None;
+                }
+                machine.interact(trade.id, &player, lng)?;
+            }
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::constants::{DESIRED_FPS, MOVEMENT_SPEED};
+    use crate::languages::Lang;
+
+    #[test]
+    fn test_movement() {
+        let mut gamestate = GameState::new_with_lang(Lang::En);
+        gamestate.start_scenario("crash_landing");
+        let start = gamestate.player.position;
+        let delta = 1. / DESIRED_FPS as f32;
+        // The player speeds up and moves diagonally as fast as straight
+        gamestate.update_movement(Vec2::new(1., 1.).normalize(), delta);
+        let speed = Vec2::from(gamestate.player.velocity).length();
+        assert!(speed > 0. && speed < MOVEMENT_SPEED);
+        for _ in 0..DESIRED_FPS / 4 {
+            gamestate.update_movement(Vec2::new(1., 1.).normalize(), delta);
+        }
+        let speed = Vec2::from(gamestate.player.velocity).length();
+        assert!((speed - MOVEMENT_SPEED).abs() < 0.1);
+        assert!(gamestate.player.position.0 > start.0 && gamestate.player.position.1 > start.1);
+        // Without input the player slows down until they stand still
+        for _ in 0..DESIRED_FPS {
+            gamestate.update_movement(Vec2::ZERO, delta);
+        }
+        assert_eq!(gamestate.player.velocity, (0., 0.));
+
+        // Running diagonally into the left wall the player slides along it
+        gamestate.player.position = (300., 480.);
+        for _ in 0..DESIRED_FPS {
+            gamestate.update_movement(Vec2::new(-1., 1.).normalize(), delta);
+        }
+        let (x, y) = gamestate.player.position;
+        assert!((x - 280.).abs() < 0.5, "{x}");
+        assert!(y > 600.);
+        assert!(!gamestate.collision_detection(gamestate.player.position));
+    }
+}
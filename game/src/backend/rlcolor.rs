@@ -102,4 +102,18 @@ impl RLColor {
         b: 0.,
         a: 1.,
     };
+    /// Color: tint of upgraded machines without their own sprites
+    pub const TIER_TINT: Color = Color {
+        r: 0.6,
+        g: 0.85,
+        b: 1.,
+        a: 1.,
+    };
+    /// Color: the surface of mars
+    pub const MARS: Color = Color {
+        r: 0.55,
+        g: 0.27,
+        b: 0.18,
+        a: 1.,
+    };
 }
@@ -1,4 +1,5 @@
 //! Miscellaneous utilities used by the backend.
+use crate::backend::collision::Shape;
 use crate::backend::constants::{PLAYER_ICON_SIZE, SCREEN_RESOLUTION};
 use ggez::glam::Vec2;
 use ggez::graphics::{Color, Rect};
@@ -22,11 +23,14 @@ pub fn get_scale(ctx: &Context) -> Vec2 {
 /// # Returns
 /// * `true` if the player collides with an area
 /// * `false` if the player does not collide with an area
-pub fn is_colliding(player_pos: (usize, usize), area: &Rect) -> bool {
-    area.x < player_pos.0 as f32 + PLAYER_ICON_SIZE.0 as f32
-        && area.x + area.w > player_pos.0 as f32
-        && area.y < player_pos.1 as f32 + PLAYER_ICON_SIZE.1 as f32
-        && area.y + area.h > player_pos.1 as f32
+pub fn is_colliding(player_pos: (f32, f32), area: &Rect) -> bool {
+    let player = Rect::new(
+        player_pos.0,
+        player_pos.1,
+        PLAYER_ICON_SIZE.0,
+        PLAYER_ICON_SIZE.1,
+    );
+    Shape::from(player).overlaps(&Shape::from(*area))
 }
 
 /// This macro is used for simplifying drawing with scaling.
@@ -0,0 +1,242 @@
+//! This File contains the area outside of the station, the suit oxygen of the player and the scavenge points
+use crate::backend::collision::Shape;
+use crate::backend::constants::{
+    CONDITION_STATUS_DURATION, DESIRED_FPS, PLAYER_INTERACTION_RADIUS, SCAVENGE_COOLDOWN,
+    SCAVENGE_POINT_SIZE, SUIT_INFO_POSITION, SUIT_OXYGEN_MAX, SUIT_OXYGEN_REFILL,
+};
+use crate::backend::gamestate::{GameCommand, GameState};
+use crate::backend::rlcolor::RLColor;
+use crate::backend::screen::{Popup, ScreenCommand, StackCommand};
+use crate::backend::utils::get_draw_params;
+use crate::game_core::item::ItemId;
+use crate::game_core::map::Tile;
+use crate::game_core::resources::ResourceKind;
+use crate::game_core::status_effect::StatusKind;
+use crate::languages::{scavenge, suit_oxygen};
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, DrawMode, Mesh, MeshBuilder, Text, TextFragment};
+use ggez::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// A place outside of the station where the player can scavenge items.
+/// After it was scavenged it takes a while until there is something to find again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ScavengePoint {
+    /// The position of the top left corner of the scavenge point in px
+    pub(crate) position: (f32, f32),
+    /// Everything the player can find, one of them is chosen at random.
+    /// Every loot contains the amount of the items the player finds.
+    pub(crate) loot: Vec<BTreeMap<ItemId, i32>>,
+}
+
+impl ScavengePoint {
+    /// Returns the shape of the scavenge point, which is a circle
+    pub(crate) fn shape(&self) -> Shape {
+        Shape::Circle {
+            center: Vec2::new(
+                self.position.0 + SCAVENGE_POINT_SIZE / 2.,
+                self.position.1 + SCAVENGE_POINT_SIZE / 2.,
+            ),
+            radius: SCAVENGE_POINT_SIZE / 2.,
+        }
+    }
+
+    /// Returns the area the player has to stand in to scavenge
+    pub(crate) fn interaction_shape(&self) -> Shape {
+        let Shape::Circle { center, radius } = self.shape() else {
+            unreachable!("scavenge points are circles");
+        };
+        Shape::Circle {
+            center,
+            radius: radius + PLAYER_INTERACTION_RADIUS,
+        }
+    }
+}
+
+/// The state of the area outside of the station
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct EvaState {
+    /// The in-game time at which the scavenge points were scavenged the last time, indexed by the scavenge point
+    #[serde(default)]
+    pub(crate) scavenged_at: BTreeMap<usize, u32>,
+}
+
+impl GameState {
+    /// Returns whether the player is standing outside of the station
+    pub(crate) fn is_outside(&self) -> bool {
+        self.map().tile_at(self.player.feet()) == Tile::Surface
+    }
+
+    /// Uses up the suit oxygen while the player is outside of the station and refills it from
+    /// the oxygen of the station once they are back inside. An empty suit causes hypoxia.
+    pub(crate) fn update_eva(&mut self) {
+        if self.is_outside() {
+            self.player.suit_oxygen = self.player.suit_oxygen.saturating_sub(1);
+            if self.player.suit_oxygen == 0 {
+                self.player
+                    .add_status(StatusKind::Hypoxia, CONDITION_STATUS_DURATION);
+            }
+            return;
+        }
+        // a suit can only be refilled in a room with air
+        if self
+            .player_room()
+            .is_some_and(|room| self.room_pressure(room) == 0)
+        {
+            return;
+        }
+        let refill = SUIT_OXYGEN_REFILL
+            .min(SUIT_OXYGEN_MAX - self.player.suit_oxygen)
+            .min(self.player.resources[ResourceKind::Oxygen]);
+        self.player.suit_oxygen += refill;
+        self.player.resources[ResourceKind::Oxygen] -= refill;
+    }
+
+    /// Returns whether there is something to find at the scavenge point with the given index
+    /// # Arguments
+    /// * `point` - The index of the scavenge point
+    pub(crate) fn is_scavenge_point_ready(&self, point: usize) -> bool {
+        self.eva.scavenged_at.get(&point).is_none_or(|time| {
+            self.player.time.saturating_sub(*time) >= SCAVENGE_COOLDOWN * DESIRED_FPS
+        })
+    }
+
+    /// Scavenges the scavenge point with the given index, the player receives one of its loots at random
+    /// # Arguments
+    /// * `point` - The index of the scavenge point
+    /// # Returns
+    /// * `RLResult` - validates if the items and the popup were sent successfully
+    pub(crate) fn scavenge(&mut self, point: usize) -> RLResult {
+        let Some(definition) = self.map().scavenge_points.get(point) else {
+            return Ok(());
+        };
+        let text = scavenge(self.lng);
+        if !self.is_scavenge_point_ready(point) || definition.loot.is_empty() {
+            return self.send_popup(text[1].to_string());
+        }
+        let loot = &definition.loot[fastrand::usize(..definition.loot.len())];
+        info!("Scavenged {:?} at scavenge point {}", loot, point);
+        let items = loot
+            .iter()
+            .filter(|(_, amount)| **amount > 0)
+            .map(|(id, amount)| (id.item(self.lng), *amount))
+            .collect::<Vec<_>>();
+        self.eva.scavenged_at.insert(point, self.player.time);
+        let found = items
+            .iter()
+            .map(|(item, amount)| format!("{amount} {}", item.name))
+            .collect::<Vec<_>>();
+        if let Some(sender) = self.sender.as_ref() {
+            sender.send(GameCommand::AddItems(items))?;
+        }
+        self.send_popup(format!("{}\n{}", text[0], found.join("\n")))
+    }
+
+    /// Shows a popup with the given message
+    /// # Arguments
+    /// * `message` - The message of the popup
+    fn send_popup(&mut self, message: String) -> RLResult {
+        self.get_screen_sender()?
+            .send(StackCommand::Screen(ScreenCommand::Popup(Popup::info(
+                message,
+            ))))?;
+        Ok(())
+    }
+
+    /// Draws the scavenge points, points with nothing to find are greyed out,
+    /// and the suit oxygen while the player is outside or it is not full
+    /// # Arguments
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// * `RLResult` - validates if the meshes were created successfully
+    pub(crate) fn draw_eva(&self, canvas: &mut Canvas, scale: Vec2, ctx: &Context) -> RLResult {
+        let points = &self.map().scavenge_points;
+        if !points.is_empty() {
+            let mut builder = MeshBuilder::new();
+            for (index, point) in points.iter().enumerate() {
+                let color = if self.is_scavenge_point_ready(index) {
+                    RLColor::GOLD
+                } else {
+                    RLColor::DARK_GREY
+                };
+                if let Shape::Circle { center, radius } = point.shape() {
+                    builder.circle(DrawMode::fill(), center, radius, 0.5, color)?;
+                }
+            }
+            let mesh = Mesh::from_data(ctx, builder.build());
+            draw!(canvas, &mesh, Vec2::ZERO, scale);
+        }
+
+        if self.is_outside() || self.player.suit_oxygen < SUIT_OXYGEN_MAX {
+            let percent = u32::from(self.player.suit_oxygen) * 100 / u32::from(SUIT_OXYGEN_MAX);
+            let mut text = Text::new(
+                TextFragment::new(format!("{}: {percent}%", suit_oxygen(self.lng)))
+                    .color(RLColor::LIGHT_BLUE),
+            );
+            text.set_scale(22.);
+            draw!(
+                canvas,
+                &text,
+                Vec2::new(SUIT_INFO_POSITION.0, SUIT_INFO_POSITION.1),
+                scale
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::constants::ROOM_PRESSURE_MAX;
+    use crate::backend::screen::Screen;
+    use crate::languages::Lang;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_eva() {
+        let mut gamestate = GameState::new_with_lang(Lang::En);
+        let (sender, _receiver) = channel();
+        gamestate.set_sender(sender);
+        gamestate.start_scenario("outpost");
+        assert!(!gamestate.is_outside());
+        let airlock = gamestate
+            .map()
+            .doors
+            .iter()
+            .position(|door| door.airlock)
+            .unwrap();
+        // The rocks on the surface block the way
+        assert!(gamestate.collision_detection((180., 330.)));
+        assert!(!gamestate.collision_detection((100., 250.)));
+        // The suit is used up outside of the station
+        gamestate.player.position = (100., 250.);
+        assert!(gamestate.is_outside());
+        gamestate.update_eva();
+        assert_eq!(gamestate.player.suit_oxygen, SUIT_OXYGEN_MAX - 1);
+        // An open airlock lets the air escape
+        gamestate.toggle_door(airlock);
+        gamestate.update_rooms();
+        let room = gamestate.map().doors[airlock].rooms[0];
+        assert!(gamestate.room_pressure(room) < ROOM_PRESSURE_MAX);
+
+        // Scavenging a point uses it up until its cooldown is over
+        assert!(gamestate.is_scavenge_point_ready(0));
+        gamestate.scavenge(0).unwrap();
+        assert!(!gamestate.is_scavenge_point_ready(0));
+        gamestate.player.time += SCAVENGE_COOLDOWN * DESIRED_FPS;
+        assert!(gamestate.is_scavenge_point_ready(0));
+
+        // Back inside the suit is refilled with the oxygen of the station
+        gamestate.player.position = gamestate.map().spawn().unwrap();
+        let oxygen = gamestate.player.resources[ResourceKind::Oxygen];
+        gamestate.update_eva();
+        assert_eq!(gamestate.player.suit_oxygen, SUIT_OXYGEN_MAX);
+        assert_eq!(gamestate.player.resources[ResourceKind::Oxygen], oxygen - 1);
+    }
+}
@@ -1,46 +1,83 @@
-use crate::backend::constants::{ObjectId, PopupType, DESIRED_FPS, SANDSTORM_CR};
+use crate::backend::constants::{EventId, ObjectId, PopupType, TradeId, DESIRED_FPS};
 use crate::backend::gamestate::GameState;
 use crate::backend::screen::{Popup, ScreenCommand, StackCommand};
 use crate::game_core::resources::Resources;
-use crate::languages::{
-    comet_strike, informations_popup_mars, informations_popup_nasa, mars_info, nasa_info,
-    power_failure, sandstorm, warnings, Lang,
-};
+use crate::game_core::scheduler::EventTable;
+use crate::game_core::status_effect::StatusKind;
+use crate::languages::{mars_info, nasa_info, warnings, Lang};
 use crate::machines::machine::State;
 use crate::RLResult;
-use ggez::Context;
 use serde::{Deserialize, Serialize};
 use std::sync::mpsc::Sender;
 use tracing::info;
 
+/// An effect an event has on the game while it is active
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub(crate) enum EventEffect {
+    /// Changes the change rate of the player's resources as long as the event is active
+    ResourceChange(Resources<i16>),
+    /// Opens a hull breach with the given severity
+    OpenBreach(u8),
+    /// Stops the machine with the given id if it is running
+    StopMachine(ObjectId),
+    /// Breaks the machine with the given id, it has to be repaired again
+    BreakMachine(ObjectId),
+    /// Unlocks the trade with the given id while the event is active, used for mitigation trades
+    EnableTrade(TradeId),
+    /// Applies a status effect with the given duration in seconds to the player
+    ApplyStatus(StatusKind, u32),
+}
+
+/// Returns the given share of a resource change
+/// # Arguments
+/// * `change` - The complete resource change
+/// * `percent` - The share in percent
+fn share(change: Resources<i16>, percent: u8) -> Resources<i16> {
+    let scale =
+        |value: i16| i16::try_from(i32::from(value) * i32::from(percent) / 100).unwrap_or(value);
+    change.into_iter().map(scale).collect()
+}
+
 /// defines an event which has an impact on the game and the player
 /// for example a popup or a change in the player's resources
 /// events can just fade off or stay as long as the player didnt interact with them
-
-/// Defines an event in the game
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub(crate) struct Event {
+    /// Stable identifier of the event which does not depend on the language
+    #[serde(default)]
+    pub(crate) id: EventId,
     name: String,
     info_text: String,
-    pub(crate) resources: Option<Resources<i16>>,
+    /// Contains the effects of the event, they are applied when the event starts
+    #[serde(default)]
+    pub(crate) effects: Vec<EventEffect>,
+    /// The strength of the effects in percent, it starts at 100 and is lowered by mitigation trades
+    #[serde(default = "Event::full_strength")]
+    pub(crate) strength: u8,
     duration: u32,
     popup_type: PopupType,
     popup_message: String,
+    /// Resource change of events from older saves, it was subtracted from the player's change rate when the event started.
+    /// It is converted into an effect when the save is loaded, see `Event::migrate_legacy_resources`
+    #[serde(default, skip_serializing)]
+    resources: Option<Resources<i16>>,
 }
 
 impl Event {
     /// create new event
     /// # Arguments
+    /// * `id` - the stable identifier of the event
     /// * `event` - name and info text of the event
-    /// * `resources` - resources which are affected by the event
-    /// * `duration` - duration of the event in seconds
-    /// * `popup_type` - type of the popup which is shown when the event starts
     /// * `popup_message` - message of the popup which is shown when the event starts
+    /// * `popup_type` - type of the popup which is shown when the event starts
+    /// * `effects` - effects the event has on the game
+    /// * `duration` - duration of the event in seconds
     pub fn new(
+        id: EventId,
         event: [&str; 2],
         popup_message: &str,
         popup_type: PopupType,
-        resources: Option<Resources<i16>>,
+        effects: Vec<EventEffect>,
         duration: u32,
     ) -> Self {
         info!(
@@ -49,57 +86,66 @@ impl Event {
             event[1].to_string()
         );
         Self {
+            id,
             name: event[0].to_string(),
             info_text: event[1].to_string(),
-            resources,
+            effects,
+            strength: 0,
             duration: duration * DESIRED_FPS,
             popup_type,
             popup_message: popup_message.to_string(),
+            resources: None,
         }
     }
 
-    /// if no Event is active it either chooses a random event of the Event enum or nothing every 60 seconds
-    #[allow(clippy::pedantic)]
-    pub fn event_generator(lng: Lang) -> Option<Event> {
+    /// Converts the resource change of an event from an older save into a `ResourceChange` effect.
+    /// # Returns
+    /// The resource change which has to be given back to the player's change rate, if the event had one
+    pub(crate) fn migrate_legacy_resources(&mut self) -> Option<Resources<i16>> {
+        let resources = self.resources.take()?;
+        self.effects.push(EventEffect::ResourceChange(
+            Resources::default() - resources,
+        ));
+        Some(resources)
+    }
+
+    /// The strength of an event which is neither mitigated nor escalated
+    pub(crate) const FULL_STRENGTH: u8 = 100;
+
+    /// Returns the strength of an event which is not mitigated
+    fn full_strength() -> u8 {
+        Self::FULL_STRENGTH
+    }
+
+    /// Creates the event with the given id.
+    /// The effects and the duration are taken from the event definitions, the texts from the current language.
+    /// # Arguments
+    /// * `id` - The id of the event
+    /// * `table` - The event table which contains the definition of the event
+    /// * `lng` - The language of the texts of the event
+    pub(crate) fn from_id(id: EventId, table: &EventTable, lng: Lang) -> Event {
         let rng = fastrand::Rng::new();
-        match rng.usize(..15) {
-            8 => Some(Event::new(
-                *sandstorm(lng),
-                warnings(lng)[2],
-                PopupType::Warning,
-                Some(SANDSTORM_CR),
-                5,
-            )),
-            0 | 3 => Some(Event::new(
-                *comet_strike(lng),
-                warnings(lng)[0],
-                PopupType::Warning,
-                None,
-                0,
-            )),
-            1 => Some(Event::new(
-                *informations_popup_nasa(lng),
-                nasa_info(lng)[rng.usize(..4)],
-                PopupType::Nasa,
-                None,
-                0,
-            )),
-            2 | 9 | 7 => Some(Event::new(
-                *power_failure(lng),
-                warnings(lng)[1],
-                PopupType::Warning,
-                None,
-                0,
-            )),
-            4 => Some(Event::new(
-                *informations_popup_mars(lng),
-                mars_info(lng)[rng.usize(..5)],
-                PopupType::Mars,
-                None,
-                0,
-            )),
-            _ => None,
-        }
+        let (popup_message, popup_type) = match id {
+            EventId::Sandstorm => (warnings(lng)[2], PopupType::Warning),
+            EventId::CometStrike => (warnings(lng)[0], PopupType::Warning),
+            EventId::InformationNasa => (nasa_info(lng)[rng.usize(..4)], PopupType::Nasa),
+            EventId::PowerFailure => (warnings(lng)[1], PopupType::Warning),
+            EventId::InformationMars => (mars_info(lng)[rng.usize(..5)], PopupType::Mars),
+            EventId::SeismicTremor => (warnings(lng)[4], PopupType::Warning),
+            EventId::Fire => (warnings(lng)[5], PopupType::Warning),
+            EventId::StormDamage => (warnings(lng)[6], PopupType::Warning),
+        };
+        let (effects, duration) = table.get(id).map_or((vec![], 0), |definition| {
+            (definition.effects.clone(), definition.duration)
+        });
+        Event::new(
+            id,
+            id.texts(lng),
+            popup_message,
+            popup_type,
+            effects,
+            duration,
+        )
     }
 
     /// Sends a popup of an event to the screen
@@ -135,61 +181,105 @@ impl Event {
         self.duration != 0
     }
 
-    /// Triggers the event and activates its effect
+    /// Returns the change of the player's resources caused by this event at its current strength
+    pub(crate) fn resource_modifier(&self) -> Resources<i16> {
+        self.effects
+            .iter()
+            .filter_map(|effect| match *effect {
+                EventEffect::ResourceChange(change) => Some(share(change, self.strength)),
+                _ => None,
+            })
+            .fold(Resources::default(), |sum, change| sum + change)
+    }
+
+    /// Changes the strength of the event and applies the difference to the game.
+    /// Raising the strength of an event that did not start yet triggers it, lowering it to 0 ends its effects.
+    /// Effects which change the game once, like breaches or broken machines, are only applied when the event starts.
     /// # Arguments
-    /// * `restore` - If true the event will be deactivated and the resources will be restored
+    /// * `strength` - The new strength of the effects in percent
     /// * `gamestate` - The gamestate which is used to access the player and the machines
-    pub fn action(&self, restore: bool, gamestate: &mut GameState) -> RLResult {
-        let lng = gamestate.lng;
-        let comet_strike: &str = comet_strike(lng)[0];
-        let power_failure: &str = power_failure(lng)[0];
+    pub fn action(&mut self, strength: u8, gamestate: &mut GameState) -> RLResult {
         let sender = gamestate.get_screen_sender()?.clone();
-
-        // handle event effects
-        match self.name.as_str() {
-            s if comet_strike == s => {
-                if let Some(one_hole) = gamestate
-                    .machines
-                    .iter_mut()
-                    .find(|machine| machine.id.is_hole() && machine.state != State::Running)
-                {
-                    // event not triggered if both machine are already running
-                    Event::send_popup(&self.popup_message, &sender, self.popup_type, &self.name)
-                        .unwrap();
-                    one_hole.change_state_to(&State::Running);
+        let starting = self.strength == 0 && strength > 0;
+        // events without effects only inform the player, other events are only shown if they had an effect
+        let mut triggered = self.effects.is_empty();
+        for effect in &self.effects {
+            match *effect {
+                // resource changes are applied as modifiers every tick, see `Event::resource_modifier`
+                EventEffect::ResourceChange(_) => triggered = true,
+                EventEffect::EnableTrade(id) => {
+                    gamestate.set_trade_locked(id, strength == 0);
                 }
-            }
-            s if s == power_failure => {
-                gamestate.machines.iter_mut().for_each(|machine| {
-                    // if machine is running it will b use tracing::{info, Id};e stopped
-                    // event not triggered if machine is broken or idling
-                    if machine.id == ObjectId::PowerGenerator && machine.state == State::Running {
-                        Event::send_popup(
-                            &self.popup_message,
-                            &sender,
-                            self.popup_type,
-                            &self.name,
-                        )
-                        .unwrap();
+                // breaches and stopped machines stay until the player repairs them
+                // stronger events open more severe breaches
+                EventEffect::OpenBreach(severity) if starting => {
+                    let severity = (u16::from(severity) * u16::from(strength))
+                        .div_ceil(u16::from(Self::FULL_STRENGTH));
+                    triggered |= gamestate.spawn_breach(u8::try_from(severity).unwrap_or(u8::MAX));
+                }
+                EventEffect::StopMachine(id) if starting => {
+                    for machine in gamestate
+                        .machines
+                        .iter_mut()
+                        .filter(|machine| machine.id == id && machine.state == State::Running)
+                    {
                         machine.change_state_to(&State::Idle);
+                        triggered = true;
                     }
-                });
-            }
-            // apply direct resource changes if there are any and the event is not handled above
-            _ => {
-                Event::send_popup(&self.popup_message, &sender, self.popup_type, &self.name)?;
-                if let Some(resources) = self.resources {
-                    if restore {
-                        gamestate.player.resources_change =
-                            gamestate.player.resources_change + resources;
-                    } else {
-                        gamestate.player.resources_change =
-                            gamestate.player.resources_change - resources;
+                }
+                EventEffect::BreakMachine(id) if starting => {
+                    for machine in gamestate
+                        .machines
+                        .iter_mut()
+                        .filter(|machine| machine.id == id && machine.state != State::Broken)
+                    {
+                        machine.change_state_to(&State::Broken);
+                        triggered = true;
                     }
                 }
+                EventEffect::ApplyStatus(kind, duration) if starting => {
+                    gamestate.player.add_status(kind, duration);
+                }
+                EventEffect::OpenBreach(_)
+                | EventEffect::StopMachine(_)
+                | EventEffect::BreakMachine(_)
+                | EventEffect::ApplyStatus(..) => {}
+            }
+        }
+        if triggered && starting {
+            Event::send_popup(&self.popup_message, &sender, self.popup_type, &self.name)?;
+        }
+        info!(
+            "Event {} changed strength from {} to {}",
+            self.name, self.strength, strength
+        );
+        self.strength = strength;
+        Ok(())
+    }
+
+    /// Weakens the active event with the given id relative to its current strength, an event that is weakened to 0 is over.
+    /// Every event can only be mitigated once, so its mitigation trades are locked afterwards.
+    /// # Arguments
+    /// * `id` - The id of the event
+    /// * `percent` - The share of the current strength in percent the event is weakened by
+    /// * `gamestate` - The gamestate which contains the event
+    pub fn mitigate_event(id: EventId, percent: u8, gamestate: &mut GameState) -> RLResult {
+        let Some(index) = gamestate.events.iter().position(|event| event.id == id) else {
+            return Ok(());
+        };
+        let mut event = gamestate.events.remove(index);
+        // escalated events are stronger than 100%, so a mitigation scales with the strength
+        let remaining = u16::from(event.strength) * u16::from(100 - percent.min(100)) / 100;
+        event.action(u8::try_from(remaining).unwrap_or(u8::MAX), gamestate)?;
+        for effect in &event.effects {
+            if let EventEffect::EnableTrade(trade) = *effect {
+                gamestate.set_trade_locked(trade, true);
             }
         }
-        info!("Event triggered (restore: {}): {}", restore, self.name);
+        if event.strength == 0 {
+            event.duration = 0;
+        }
+        gamestate.events.insert(index, event);
         Ok(())
     }
 
@@ -198,47 +288,21 @@ impl Event {
         self.name.clone()
     }
 
-    /// Deletes due events from the gamestates events vector and adds new events
+    /// Deletes due events from the gamestates events vector and lets the scheduler add new events
     /// # Arguments
     /// * `gamestate` - The gamestate which is used to access the events vector
-    /// * `context` - The game context which is used to access the current tick
-    pub fn update_events(ctx: &Context, gamestate: &mut GameState) -> RLResult {
-        let lng = gamestate.lng;
-        if ctx.time.ticks() % 20 == 0 {
-            gamestate.events.iter_mut().for_each(|event| {
-                event.duration = event.duration.saturating_sub(20);
-                if event.name == "Sandsturm" {}
-            });
-            // restore resources of inactive events
-            for event in &gamestate.events {
-                if !event.is_active() {
-                    if let Some(resources) = event.resources {
-                        gamestate.player.resources_change =
-                            gamestate.player.resources_change + resources;
-                    }
-                }
-            }
-            // remove all events which are not active anymore
-            gamestate.events.retain(|event| {
-                if event.is_active() {
-                    true
-                } else {
-                    info!("Event {} is not active anymore", event.get_name());
-                    false
-                }
-            });
+    pub fn update_events(gamestate: &mut GameState) -> RLResult {
+        gamestate.events.iter_mut().for_each(|event| {
+            event.duration = event.duration.saturating_sub(1);
+        });
+        // restore resources of inactive events and remove them
+        let (active, inactive): (Vec<_>, Vec<_>) =
+            gamestate.events.drain(..).partition(Event::is_active);
+        gamestate.events = active;
+        for mut event in inactive {
+            info!("Event {} is not active anymore", event.get_name());
+            event.action(0, gamestate)?;
         }
-        // have a maximum of one active event
-        if ctx.time.ticks() >= 400 && ctx.time.ticks() % 200 == 0 {
-            // generate new event
-            // might not return an event
-            let gen_event = Event::event_generator(lng);
-            // if event is not none, add it to the gamestates events vector and activate apply its effect
-            if let Some(event) = gen_event {
-                event.action(false, gamestate)?;
-                gamestate.events.push(event);
-            }
-        }
-        Ok(())
+        gamestate.schedule_events()
     }
 }
@@ -1,9 +1,12 @@
-use crate::backend::gamestate::{GameCommand, GameState};
+use crate::backend::constants::{Difficulty, GameMode, DESIRED_FPS};
+use crate::backend::gamestate::GameState;
 use crate::backend::screen::{Screen, ScreenCommand, StackCommand};
 use crate::backend::utils::{get_draw_params, get_scale};
+use crate::game_core::scenario::default_scenario_id;
 use crate::languages::{
     additional_info_string, air_and_energy_string, air_string, button_info, death_reason_string,
-    energy_string, intro_text, tutorial_text, winning_text, Lang,
+    energy_string, hunger_string, injury_string, intro_text, survival_time, thirst_string,
+    tutorial_text, winning_text, Lang,
 };
 use crate::main_menu::main_menu::MainMenu;
 use crate::{draw, RLResult};
@@ -20,6 +23,9 @@ pub enum DeathReason {
     Oxygen,
     Energy,
     Both,
+    Injury,
+    Hunger,
+    Thirst,
 }
 
 impl DeathReason {
@@ -28,6 +34,9 @@ impl DeathReason {
             DeathReason::Oxygen => air_string(lng),
             DeathReason::Energy => energy_string(lng),
             DeathReason::Both => air_and_energy_string(lng),
+            DeathReason::Injury => injury_string(lng),
+            DeathReason::Hunger => hunger_string(lng),
+            DeathReason::Thirst => thirst_string(lng),
         }
     }
 }
@@ -48,6 +57,12 @@ pub struct InfoScreen {
     sender: Sender<StackCommand>,
     screen_type: ScreenType,
     background_image: Option<graphics::Image>,
+    /// The difficulty the game is started with, only used by the `IntroScreen`
+    difficulty: Difficulty,
+    /// The id of the scenario the game is started with, only used by the `IntroScreen`
+    scenario: String,
+    /// The mode the game is started with, only used by the `IntroScreen`
+    mode: GameMode,
     lng: Lang,
 }
 
@@ -83,13 +98,25 @@ impl InfoScreen {
             sender,
             screen_type: screentype,
             background_image: None,
+            difficulty: Difficulty::default(),
+            scenario: default_scenario_id(),
+            mode: GameMode::default(),
             lng,
         }
     }
     /// Creates a new `IntroScreen` using `InfoScreen`
     /// # Arguments
     /// * `sender` - The sender to send the command to the `ScreenStack`
-    pub fn new_intro_screen(sender: Sender<StackCommand>, lng: Lang) -> Self {
+    /// * `difficulty` - The difficulty the new game is started with
+    /// * `scenario` - The id of the scenario the new game is started with
+    /// * `mode` - The mode the new game is started with
+    pub fn new_intro_screen(
+        sender: Sender<StackCommand>,
+        lng: Lang,
+        difficulty: Difficulty,
+        scenario: &str,
+        mode: GameMode,
+    ) -> Self {
         let mut main_message =
             graphics::Text::new(format!("{} \n{}", intro_text(lng), tutorial_text(lng)));
         main_message.set_scale(50.);
@@ -104,9 +131,26 @@ impl InfoScreen {
             sender,
             screen_type,
             background_image: None,
+            difficulty,
+            scenario: scenario.to_string(),
+            mode,
             lng,
         }
     }
+    /// Shows the time the player survived instead of the additional text, used when an endless game is over
+    /// # Arguments
+    /// * `time` - The time the player survived in ticks
+    pub fn with_survival_time(mut self, time: u32) -> Self {
+        let seconds = time / DESIRED_FPS;
+        self.additional_text = graphics::Text::new(format!(
+            "{} {}:{:02}",
+            survival_time(self.lng)[1],
+            seconds / 60,
+            seconds % 60
+        ));
+        self.additional_text.set_scale(70.);
+        self
+    }
     /// Creates a new Winning using `InfoScreen`
     /// # Arguments
     /// * `sender` - The sender to send the command to the `ScreenStack`
@@ -124,6 +168,9 @@ impl InfoScreen {
             sender,
             screen_type,
             background_image: None,
+            difficulty: Difficulty::default(),
+            scenario: default_scenario_id(),
+            mode: GameMode::default(),
             lng,
         }
     }
@@ -151,13 +198,10 @@ impl Screen for InfoScreen {
                 self.sender
                     .send(StackCommand::Screen(ScreenCommand::Push(Box::new({
                         let mut game_state = GameState::new(ctx, lng)?;
+                        game_state.difficulty = self.difficulty;
+                        game_state.mode = self.mode;
                         game_state.init(ctx)?;
-                        game_state.create_machine();
-                        game_state
-                            .sender
-                            .as_mut()
-                            .unwrap()
-                            .send(GameCommand::Milestone)?;
+                        game_state.start_scenario(&self.scenario);
                         game_state
                     }))))?;
             }
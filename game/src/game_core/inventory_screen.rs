@@ -0,0 +1,213 @@
+//! This File contains the `InventoryScreen`, which lists every item of the player with its details
+use crate::backend::constants::{gen_machine, ObjectId};
+use crate::backend::rlcolor::RLColor;
+use crate::backend::screen::{Screen, ScreenCommand, StackCommand};
+use crate::backend::utils::{get_draw_params, get_scale};
+use crate::game_core::item::Item;
+use crate::languages::{inventory, Lang};
+use crate::machines::trade::TradeEffect;
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{self, Image, Text, TextFragment};
+use ggez::winit::event::VirtualKeyCode;
+use ggez::Context;
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+
+/// Position of the first entry of the inventory
+const ENTRY_POSITION: (f32, f32) = (200., 180.);
+/// Height of one item entry
+const ENTRY_HEIGHT: f32 = 190.;
+
+/// One item of the inventory together with everything the screen shows about it
+#[derive(Debug, Clone)]
+struct InventoryEntry {
+    item: Item,
+    amount: i32,
+    icon: Option<Image>,
+    /// The recipes which use the item, formatted as `machine: trade`
+    used_by: Vec<String>,
+    /// The machines which produce the item
+    produced_by: Vec<String>,
+}
+
+/// Screen which shows every item of the player with its description and amount.
+/// For every item it lists the recipes that use it and the machines that produce it.
+/// The game is paused while the screen is open.
+#[derive(Debug)]
+pub struct InventoryScreen {
+    entries: Vec<InventoryEntry>,
+    sender: Sender<StackCommand>,
+    lng: Lang,
+}
+
+impl InventoryScreen {
+    /// Creates a new `InventoryScreen`
+    /// # Arguments
+    /// * `inventory` - The inventory of the player
+    /// * `assets` - The loaded assets of the game, used to draw the item icons
+    /// * `sender` - The sender of the screen stack
+    /// * `lng` - The current language
+    pub(crate) fn new(
+        inventory: &[(Item, i32)],
+        assets: &HashMap<String, Image>,
+        sender: Sender<StackCommand>,
+        lng: Lang,
+    ) -> Self {
+        let entries = inventory
+            .iter()
+            .map(|(item, amount)| InventoryEntry {
+                item: item.clone(),
+                amount: *amount,
+                icon: assets.get(item.img.as_str()).cloned(),
+                used_by: recipes_using(item, lng),
+                produced_by: producers_of(item, lng),
+            })
+            .collect();
+        Self {
+            entries,
+            sender,
+            lng,
+        }
+    }
+}
+
+/// Returns every recipe of every machine which uses the given item.
+/// Storing items in a machine is not considered a recipe.
+/// # Arguments
+/// * `item` - The item to look for
+/// * `lng` - The current language
+/// # Returns
+/// * `Vec<String>` - The recipes formatted as `machine: trade`
+fn recipes_using(item: &Item, lng: Lang) -> Vec<String> {
+    let mut recipes: Vec<String> = ObjectId::ALL
+        .into_iter()
+        .flat_map(|id| {
+            gen_machine(id, (0., 0.), lng)
+                .trades
+                .into_iter()
+                .filter(|trade| !trade.effects.contains(&TradeEffect::Deposit))
+                .filter(|trade| trade.items_cost().any(|(cost, _)| cost.img == item.img))
+                .map(move |trade| format!("{}: {}", id.t(lng), trade.id.t(lng)))
+        })
+        .collect();
+    // trades that exist for several machine states are only listed once
+    recipes.dedup();
+    recipes
+}
+
+/// Returns every machine which produces the given item.
+/// Taking items out of a machine does not count as producing them.
+/// # Arguments
+/// * `item` - The item to look for
+/// * `lng` - The current language
+/// # Returns
+/// * `Vec<String>` - The names of the machines
+fn producers_of(item: &Item, lng: Lang) -> Vec<String> {
+    ObjectId::ALL
+        .into_iter()
+        .filter(|&id| {
+            gen_machine(id, (0., 0.), lng).trades.iter().any(|trade| {
+                !trade.effects.contains(&TradeEffect::Withdraw)
+                    && trade.items_yield().any(|(out, _)| out.img == item.img)
+            })
+        })
+        .map(|id| id.t(lng).to_string())
+        .collect()
+}
+
+/// Joins the given names or returns the placeholder if there are none
+fn join_or(names: &[String], placeholder: &str) -> String {
+    if names.is_empty() {
+        placeholder.to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+impl Screen for InventoryScreen {
+    /// Closes the inventory if I or Escape is pressed
+    /// # Arguments
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// `RLResult` - Returns an `RLResult`.
+    fn update(&mut self, ctx: &mut Context) -> RLResult {
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::I)
+            || ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Escape)
+        {
+            self.sender.send(StackCommand::Screen(ScreenCommand::Pop))?;
+        }
+        Ok(())
+    }
+
+    /// Draws every item with its icon, name, amount, description, recipes and producers
+    /// # Arguments
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// `RLResult` - Returns an `RLResult`.
+    fn draw(&self, ctx: &mut Context) -> RLResult {
+        let scale = get_scale(ctx);
+        let mut canvas = graphics::Canvas::from_frame(ctx, RLColor::GREY);
+        let text = inventory(self.lng);
+
+        let mut title = Text::new(TextFragment::new(text[0]).color(RLColor::BLACK));
+        title.set_scale(50.);
+        draw!(canvas, &title, Vec2::new(ENTRY_POSITION.0, 80.), scale);
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let position = Vec2::new(ENTRY_POSITION.0, ENTRY_POSITION.1 + ENTRY_HEIGHT * i as f32);
+            if let Some(icon) = &entry.icon {
+                draw!(canvas, icon, position, scale);
+            }
+            let mut name = Text::new(
+                TextFragment::new(format!("{} x{}", entry.item.name, entry.amount))
+                    .color(RLColor::BLACK),
+            );
+            name.set_scale(32.);
+            draw!(canvas, &name, position + Vec2::new(80., 0.), scale);
+            let mut details = Text::new(
+                TextFragment::new(format!(
+                    "{}\n{}: {}\n{}: {}",
+                    entry.item.info_text(),
+                    text[1],
+                    join_or(&entry.used_by, text[3]),
+                    text[2],
+                    join_or(&entry.produced_by, text[3]),
+                ))
+                .color(RLColor::BLACK),
+            );
+            details.set_scale(24.);
+            draw!(canvas, &details, position + Vec2::new(80., 40.), scale);
+        }
+
+        let mut controls = Text::new(TextFragment::new(text[4]).color(RLColor::BLACK));
+        controls.set_scale(24.);
+        draw!(canvas, &controls, Vec2::new(ENTRY_POSITION.0, 1000.), scale);
+
+        canvas.finish(ctx)?;
+        Ok(())
+    }
+
+    fn set_sender(&mut self, sender: Sender<StackCommand>) {
+        self.sender = sender;
+    }
+
+    fn lang(&self) -> Lang {
+        self.lng
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::languages::super_glue;
+
+    #[test]
+    fn test_item_details() {
+        let glue = Item::new(*super_glue(Lang::En));
+        let used_by = recipes_using(&glue, Lang::En);
+        assert!(used_by.contains(&"Hole: Seal hole".to_string()));
+        assert!(!used_by.iter().any(|recipe| recipe.contains("Store")));
+        assert_eq!(producers_of(&glue, Lang::En), vec!["work machine"]);
+    }
+}
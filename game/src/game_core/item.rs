@@ -1,6 +1,38 @@
+use crate::backend::constants::ITEM_PROPERTIES;
+use crate::languages::{petrol, printed_part, scrap, super_glue, Lang};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
+/// The kind of an item, used as key when items are defined in data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ItemId {
+    SuperGlue,
+    Petrol,
+    PrintedPart,
+    Scrap,
+}
+
+impl ItemId {
+    /// Contains every kind of item in the order of the inventory
+    pub(crate) const ALL: [ItemId; 4] = [
+        ItemId::SuperGlue,
+        ItemId::Petrol,
+        ItemId::PrintedPart,
+        ItemId::Scrap,
+    ];
+
+    /// Returns the item of this kind in the given language
+    pub(crate) fn item(self, lng: Lang) -> Item {
+        Item::new(*match self {
+            ItemId::SuperGlue => super_glue(lng),
+            ItemId::Petrol => petrol(lng),
+            ItemId::PrintedPart => printed_part(lng),
+            ItemId::Scrap => scrap(lng),
+        })
+    }
+}
+
 /// Defines an item in the inventory of the player
 /// Contains the name of the item, information about the item and the image
 #[derive(Clone, Default, Eq, Debug, PartialEq, Serialize, Deserialize)]
@@ -24,4 +56,28 @@ impl Item {
             img: item[2].to_string(),
         }
     }
+
+    /// Returns the information text of this item
+    pub(crate) fn info_text(&self) -> &str {
+        &self.info_text
+    }
+
+    /// Returns the highest amount of this item the player can carry
+    pub(crate) fn stack_limit(&self) -> i32 {
+        self.properties().0
+    }
+
+    /// Returns the weight of one piece of this item
+    pub(crate) fn weight(&self) -> u32 {
+        self.properties().1
+    }
+
+    /// Returns the stack limit and the weight of this item.
+    /// Items without an entry in `ITEM_PROPERTIES` have no stack limit and no weight.
+    fn properties(&self) -> (i32, u32) {
+        ITEM_PROPERTIES
+            .iter()
+            .find(|(img, _, _)| *img == self.img)
+            .map_or((i32::MAX, 0), |&(_, limit, weight)| (limit, weight))
+    }
 }
@@ -0,0 +1,529 @@
+//! This File contains the tile maps, which describe the floors, walls, doors and machines of a station
+use crate::backend::collision::Shape;
+use crate::backend::constants::{ObjectId, PLAYER_ICON_SIZE};
+use crate::backend::gamestate::GameState;
+use crate::backend::rlcolor::RLColor;
+use crate::backend::utils::get_draw_params;
+use crate::game_core::eva::ScavengePoint;
+use crate::game_core::objective::LocalizedText;
+use crate::game_core::scenario::default_map;
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{self, Canvas, DrawMode, Mesh, MeshBuilder, Rect};
+use ggez::Context;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::fs::{self, read_dir};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// One tile of a map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Tile {
+    /// Outside of the station, nothing can be there
+    Outside,
+    Floor,
+    Wall,
+    Door,
+    /// Floor where the player spawns at the start of a game
+    Spawn,
+    /// The surface of Mars outside of the station, the player can walk there in a suit
+    Surface,
+}
+
+impl Tile {
+    /// Returns the tile with the given character of the map format
+    fn from_char(character: char) -> Option<Self> {
+        match character {
+            ' ' => Some(Tile::Outside),
+            '.' => Some(Tile::Floor),
+            '#' => Some(Tile::Wall),
+            'D' => Some(Tile::Door),
+            'S' => Some(Tile::Spawn),
+            ',' => Some(Tile::Surface),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the player can walk on this tile
+    pub(crate) fn is_walkable(self) -> bool {
+        matches!(self, Tile::Floor | Tile::Door | Tile::Spawn | Tile::Surface)
+    }
+
+    /// Returns whether machines can be built on this tile
+    fn is_buildable(self) -> bool {
+        matches!(self, Tile::Floor | Tile::Spawn)
+    }
+
+    /// Returns the color the tile is drawn with if the map has no background image
+    fn color(self) -> Option<graphics::Color> {
+        match self {
+            Tile::Outside => None,
+            Tile::Floor | Tile::Spawn => Some(RLColor::LIGHT_GREY),
+            Tile::Wall => Some(RLColor::DARK_GREY),
+            Tile::Door => Some(RLColor::GOLD),
+            Tile::Surface => Some(RLColor::MARS),
+        }
+    }
+}
+
+/// A machine which is placed on the map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct MapMachine {
+    pub(crate) id: ObjectId,
+    /// The position of the top left corner of the machine in px
+    pub(crate) position: (f32, f32),
+}
+
+/// A room of a map, every floor tile which can be reached from its tile without passing a wall or a door belongs to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RoomDefinition {
+    /// Stable identifier of the room
+    pub(crate) id: String,
+    pub(crate) name: LocalizedText,
+    /// Column and row of one floor tile of the room
+    pub(crate) tile: (usize, usize),
+}
+
+/// A door of a map, made of adjacent door tiles
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Door {
+    /// The area covered by the door tiles in px
+    pub(crate) area: Rect,
+    /// The indices of the rooms the door connects
+    pub(crate) rooms: Vec<usize>,
+    /// An airlock leads outside of the station, while it is open the air of its rooms escapes
+    pub(crate) airlock: bool,
+}
+
+/// Describes the layout of a station as a grid of tiles together with its machines
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TileMap {
+    /// Stable identifier of the map, used by the scenarios
+    pub(crate) id: String,
+    /// Size of a tile in px
+    pub(crate) tile_size: f32,
+    /// The name of the asset which is drawn below the map, if there is none the tiles are drawn
+    #[serde(default)]
+    pub(crate) background: Option<String>,
+    /// The rows of tiles, starting at the top of the map
+    #[serde(deserialize_with = "deserialize_tiles")]
+    pub(crate) tiles: Vec<Vec<Tile>>,
+    /// The machines of the station
+    #[serde(default)]
+    pub(crate) machines: Vec<MapMachine>,
+    /// Positions on the walls where hull breaches can open
+    #[serde(default)]
+    pub(crate) breach_positions: Vec<(f32, f32)>,
+    /// The rooms of the station, maps without rooms have no air pressure
+    #[serde(default)]
+    pub(crate) rooms: Vec<RoomDefinition>,
+    /// The places outside of the station where the player can scavenge items
+    #[serde(default)]
+    pub(crate) scavenge_points: Vec<ScavengePoint>,
+    /// Rocks the player can not walk through, each rock is a convex polygon given by its corners
+    #[serde(default)]
+    pub(crate) rocks: Vec<Vec<(f32, f32)>>,
+    /// Contains the index of the room of every tile, computed from the tiles when the map is loaded
+    #[serde(skip)]
+    room_grid: Vec<Vec<Option<usize>>>,
+    /// The doors of the station, computed from the tiles when the map is loaded
+    #[serde(skip)]
+    pub(crate) doors: Vec<Door>,
+}
+
+/// Reads the tiles of a map, each line of the text is one row and each character one tile
+fn deserialize_tiles<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Vec<Tile>>, D::Error> {
+    String::deserialize(deserializer)?
+        .lines()
+        .map(|row| {
+            row.chars()
+                .map(|character| {
+                    Tile::from_char(character).ok_or_else(|| {
+                        de::Error::custom(format!("unknown tile '{character}' in map"))
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl TileMap {
+    /// Returns the tile at the given column and row, everything beyond the grid is outside of the station
+    pub(crate) fn tile(&self, column: usize, row: usize) -> Tile {
+        self.tiles
+            .get(row)
+            .and_then(|tiles| tiles.get(column))
+            .copied()
+            .unwrap_or(Tile::Outside)
+    }
+
+    /// Returns the column and row of every tile the given area overlaps.
+    /// Returns None if the area reaches beyond the top or left border of the map.
+    #[allow(clippy::cast_sign_loss)]
+    fn tile_indices(&self, area: &Rect) -> Option<impl Iterator<Item = (usize, usize)>> {
+        if area.x < 0. || area.y < 0. {
+            return None;
+        }
+        let to_index = |value: f32| (value / self.tile_size) as usize;
+        let (left, top) = (to_index(area.x), to_index(area.y));
+        // areas ending exactly at the border of a tile do not overlap the next tile
+        let right = to_index((area.right() - 1.).max(area.x));
+        let bottom = to_index((area.bottom() - 1.).max(area.y));
+        Some((top..=bottom).flat_map(move |row| (left..=right).map(move |column| (column, row))))
+    }
+
+    /// Returns every tile the given area overlaps
+    fn tiles_in(&self, area: &Rect) -> Vec<Tile> {
+        self.tile_indices(area)
+            .map_or(vec![Tile::Outside], |indices| {
+                indices
+                    .map(|(column, row)| self.tile(column, row))
+                    .collect()
+            })
+    }
+
+    /// Returns the index of the room the tile at the given column and row belongs to
+    pub(crate) fn room(&self, column: usize, row: usize) -> Option<usize> {
+        self.room_grid
+            .get(row)
+            .and_then(|rooms| rooms.get(column))
+            .copied()
+            .flatten()
+    }
+
+    /// Returns the index of the first room the given area overlaps
+    pub(crate) fn room_in(&self, area: &Rect) -> Option<usize> {
+        self.tile_indices(area)?
+            .find_map(|(column, row)| self.room(column, row))
+    }
+
+    /// Returns the column and row of the tile at the given position in px
+    #[allow(clippy::cast_sign_loss)]
+    fn tile_index(&self, position: Vec2) -> Option<(usize, usize)> {
+        if position.x < 0. || position.y < 0. {
+            return None;
+        }
+        Some((
+            (position.x / self.tile_size) as usize,
+            (position.y / self.tile_size) as usize,
+        ))
+    }
+
+    /// Returns the index of the room at the given position in px
+    pub(crate) fn room_at(&self, position: Vec2) -> Option<usize> {
+        let (column, row) = self.tile_index(position)?;
+        self.room(column, row)
+    }
+
+    /// Returns the tile at the given position in px
+    pub(crate) fn tile_at(&self, position: Vec2) -> Tile {
+        self.tile_index(position)
+            .map_or(Tile::Outside, |(column, row)| self.tile(column, row))
+    }
+
+    /// Returns all tiles which are connected to the given tile by tiles matching the filter
+    /// # Arguments
+    /// * `start` - Column and row of the first tile
+    /// * `filter` - Decides which tiles are part of the area
+    fn flood_fill(
+        &self,
+        start: (usize, usize),
+        filter: impl Fn(Tile) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let mut area = vec![];
+        let mut open = vec![start];
+        while let Some((column, row)) = open.pop() {
+            if area.contains(&(column, row)) || !filter(self.tile(column, row)) {
+                continue;
+            }
+            area.push((column, row));
+            open.push((column + 1, row));
+            open.push((column, row + 1));
+            if let Some(left) = column.checked_sub(1) {
+                open.push((left, row));
+            }
+            if let Some(up) = row.checked_sub(1) {
+                open.push((column, up));
+            }
+        }
+        area
+    }
+
+    /// Computes the rooms of every tile and the doors from the tiles of the map
+    fn with_layout(mut self) -> Self {
+        self.room_grid = self
+            .tiles
+            .iter()
+            .map(|tiles| vec![None; tiles.len()])
+            .collect();
+        for (index, room) in self.rooms.iter().enumerate() {
+            for (column, row) in
+                self.flood_fill(room.tile, |tile| matches!(tile, Tile::Floor | Tile::Spawn))
+            {
+                self.room_grid[row][column] = Some(index);
+            }
+        }
+        let mut door_tiles: Vec<(usize, usize)> = vec![];
+        for (row, tiles) in self.tiles.iter().enumerate() {
+            for (column, tile) in tiles.iter().enumerate() {
+                if *tile != Tile::Door || door_tiles.contains(&(column, row)) {
+                    continue;
+                }
+                let tiles = self.flood_fill((column, row), |tile| tile == Tile::Door);
+                let neighbours = tiles
+                    .iter()
+                    .flat_map(|&(column, row)| {
+                        [
+                            Some((column + 1, row)),
+                            Some((column, row + 1)),
+                            column.checked_sub(1).map(|left| (left, row)),
+                            row.checked_sub(1).map(|up| (column, up)),
+                        ]
+                    })
+                    .flatten()
+                    .collect::<Vec<_>>();
+                let airlock = neighbours
+                    .iter()
+                    .any(|&(column, row)| self.tile(column, row) == Tile::Surface);
+                let mut rooms = neighbours
+                    .into_iter()
+                    .filter_map(|(column, row)| self.room(column, row))
+                    .collect::<Vec<_>>();
+                rooms.sort_unstable();
+                rooms.dedup();
+                let area = tiles
+                    .iter()
+                    .map(|&(column, row)| {
+                        Rect::new(
+                            column as f32 * self.tile_size,
+                            row as f32 * self.tile_size,
+                            self.tile_size,
+                            self.tile_size,
+                        )
+                    })
+                    .reduce(Rect::combine_with)
+                    .unwrap_or_default();
+                door_tiles.extend(tiles);
+                self.doors.push(Door {
+                    area,
+                    rooms,
+                    airlock,
+                });
+            }
+        }
+        self
+    }
+
+    /// Returns whether the player can stand in the given area
+    pub(crate) fn is_walkable(&self, area: &Rect) -> bool {
+        self.tiles_in(area).into_iter().all(Tile::is_walkable)
+    }
+
+    /// Returns whether a machine can be built in the given area
+    pub(crate) fn is_buildable(&self, area: &Rect) -> bool {
+        self.tiles_in(area).into_iter().all(Tile::is_buildable)
+    }
+
+    /// Returns the shapes of the rocks of the map
+    pub(crate) fn rock_shapes(&self) -> impl Iterator<Item = Shape> + '_ {
+        self.rocks
+            .iter()
+            .map(|corners| Shape::Polygon(corners.iter().map(|&corner| corner.into()).collect()))
+    }
+
+    /// Returns the position the player spawns at, which is the first spawn tile of the map
+    pub(crate) fn spawn(&self) -> Option<(f32, f32)> {
+        self.tiles.iter().enumerate().find_map(|(row, tiles)| {
+            tiles
+                .iter()
+                .position(|tile| *tile == Tile::Spawn)
+                .map(|column| (column as f32 * self.tile_size, row as f32 * self.tile_size))
+        })
+    }
+}
+
+/// Returns all maps defined in `assets/maps`, the first one is the default map
+pub(crate) fn maps() -> &'static [TileMap] {
+    static MAPS: OnceLock<Vec<TileMap>> = OnceLock::new();
+    MAPS.get_or_init(|| {
+        // the assets are next to the executable, the tests run in the directory of the crate
+        let mut paths: Vec<PathBuf> = read_dir("assets/maps")
+            .or_else(|_| read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/maps")))
+            .expect("Could not find the maps in assets/maps")
+            .map(|file| file.expect("Could not read assets/maps").path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "yaml")
+            })
+            .collect();
+        paths.sort();
+        let mut maps: Vec<TileMap> = paths
+            .iter()
+            .map(|path| {
+                let definition = fs::read_to_string(path).expect("Could not read a map definition");
+                serde_yaml::from_str::<TileMap>(&definition)
+                    .expect("The map definitions in assets/maps are invalid")
+                    .with_layout()
+            })
+            .collect();
+        maps.sort_by_key(|map| map.id != default_map());
+        maps
+    })
+}
+
+/// Returns the map with the given id, unknown ids fall back to the default map
+pub(crate) fn map(id: &str) -> &'static TileMap {
+    maps().iter().find(|map| map.id == id).unwrap_or(&maps()[0])
+}
+
+impl GameState {
+    /// Returns the map of the current game
+    pub(crate) fn map(&self) -> &'static TileMap {
+        map(&self.scenario().map)
+    }
+
+    /// Returns the area the player takes up at the given position
+    pub(crate) fn player_area(position: (f32, f32)) -> Rect {
+        Rect::new(
+            position.0,
+            position.1,
+            PLAYER_ICON_SIZE.0,
+            PLAYER_ICON_SIZE.1,
+        )
+    }
+
+    /// Draws the background of the map, or its tiles if it has no background image, and the rocks on top of it
+    /// # Arguments
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// * `RLResult` - validates if the background was found and the meshes were created successfully
+    pub(crate) fn draw_map(&self, canvas: &mut Canvas, scale: Vec2, ctx: &Context) -> RLResult {
+        let map = self.map();
+        if let Some(background) = &map.background {
+            canvas.draw(
+                self.get_asset(background)?,
+                graphics::DrawParam::default().scale(scale),
+            );
+        } else {
+            self.draw_tiles(canvas, scale, ctx)?;
+        }
+        if !map.rocks.is_empty() {
+            let mut builder = MeshBuilder::new();
+            for rock in &map.rocks {
+                let corners = rock
+                    .iter()
+                    .map(|&corner| corner.into())
+                    .collect::<Vec<Vec2>>();
+                builder.polygon(DrawMode::fill(), &corners, RLColor::DARK_GREY)?;
+            }
+            let mesh = Mesh::from_data(ctx, builder.build());
+            draw!(canvas, &mesh, Vec2::ZERO, scale);
+        }
+        Ok(())
+    }
+
+    /// Draws the tiles of the map
+    /// # Arguments
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// * `RLResult` - validates if the mesh was created successfully
+    fn draw_tiles(&self, canvas: &mut Canvas, scale: Vec2, ctx: &Context) -> RLResult {
+        let map = self.map();
+        let mut builder = MeshBuilder::new();
+        for (row, tiles) in map.tiles.iter().enumerate() {
+            for (column, tile) in tiles.iter().enumerate() {
+                let Some(color) = tile.color() else {
+                    continue;
+                };
+                builder.rectangle(
+                    DrawMode::fill(),
+                    Rect::new(
+                        column as f32 * map.tile_size,
+                        row as f32 * map.tile_size,
+                        map.tile_size,
+                        map.tile_size,
+                    ),
+                    color,
+                )?;
+            }
+        }
+        let mesh = Mesh::from_data(ctx, builder.build());
+        draw!(canvas, &mesh, Vec2::ZERO, scale);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_station_map() {
+        let station = map("station");
+        assert_eq!(station.tile(0, 0), Tile::Outside);
+        assert_eq!(station.tile(6, 5), Tile::Wall);
+        assert_eq!(station.tile(10, 10), Tile::Floor);
+        assert_eq!(station.spawn(), Some((600., 480.)));
+        assert!(station.is_walkable(&GameState::player_area((600., 480.))));
+        // Partly inside of the wall
+        assert!(!station.is_walkable(&GameState::player_area((260., 480.))));
+        assert!(!station.is_walkable(&Rect::new(-10., 500., 58., 96.)));
+        assert!(!station.breach_positions.is_empty());
+        assert_eq!(map("unknown").id, "station");
+        assert!(station.doors.is_empty());
+    }
+
+    #[test]
+    fn test_rooms_and_doors() {
+        let outpost = map("outpost");
+        assert_eq!(outpost.rooms.len(), 4);
+        let habitat = outpost.room_at(Vec2::new(500., 500.));
+        let workshop = outpost.room_at(Vec2::new(1200., 500.));
+        assert!(habitat.is_some() && workshop.is_some());
+        assert_ne!(habitat, workshop);
+        // Walls do not belong to a room
+        assert_eq!(outpost.room(6, 10), None);
+        assert_eq!(outpost.doors.len(), 4);
+        for door in &outpost.doors {
+            // An airlock leads from one room to the outside
+            assert_eq!(door.rooms.len(), if door.airlock { 1 } else { 2 });
+        }
+        assert_eq!(outpost.doors.iter().filter(|door| door.airlock).count(), 1);
+        assert_eq!(outpost.tile(0, 10), Tile::Surface);
+        assert!(outpost.is_walkable(&GameState::player_area((100., 250.))));
+        assert!(!outpost.is_buildable(&Rect::new(100., 250., 58., 96.)));
+        // Every machine and spawn is inside of a room
+        for machine in &outpost.machines {
+            let (x, y) = machine.position;
+            assert!(
+                outpost.room_at(Vec2::new(x, y)).is_some(),
+                "{:?}",
+                machine.id
+            );
+        }
+        let spawn = outpost.spawn().unwrap();
+        assert!(outpost.is_walkable(&GameState::player_area(spawn)));
+        // The rocks lie on the surface and leave the scavenge points free
+        assert!(!outpost.rocks.is_empty());
+        for rock in outpost.rock_shapes() {
+            assert!(rock.contains(rock.center()));
+            assert_eq!(outpost.tile_at(rock.center()), Tile::Surface);
+            for point in &outpost.scavenge_points {
+                assert!(!rock.overlaps(&point.interaction_shape()));
+            }
+        }
+        for &(x, y) in outpost.rocks.iter().flatten() {
+            assert_eq!(
+                outpost.tile_at(Vec2::new(x, y)),
+                Tile::Surface,
+                "({x}, {y})"
+            );
+        }
+    }
+}
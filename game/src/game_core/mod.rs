@@ -1,8 +1,17 @@
 pub(crate) mod audio;
+pub(crate) mod eva;
 pub(crate) mod event;
 pub(crate) mod infoscreen;
+pub(crate) mod inventory_screen;
 pub(crate) mod item;
+pub(crate) mod map;
+pub(crate) mod objective;
 pub(crate) mod player;
 pub(crate) mod resources;
+pub(crate) mod rooms;
+pub(crate) mod scenario;
+pub(crate) mod scheduler;
+pub(crate) mod status_effect;
+pub(crate) mod tutorial;
 
 pub use audio::AudioState;
@@ -0,0 +1,441 @@
+//! This File contains the objective graph, which describes the goals of the player and what happens once they are reached
+use crate::backend::constants::{
+    EventId, GameMode, ObjectId, TradeId, DESIRED_FPS, TRACKER_POSITION,
+};
+use crate::backend::gamestate::GameState;
+use crate::backend::rlcolor::RLColor;
+use crate::backend::screen::{ScreenCommand, StackCommand};
+use crate::backend::utils::get_draw_params;
+use crate::game_core::infoscreen::InfoScreen;
+use crate::game_core::resources::ResourceKind;
+use crate::languages::{objective_tracker, Lang};
+use crate::machines::machine::State;
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, Text, TextFragment};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use tracing::info;
+
+/// A text which is given in every language
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct LocalizedText {
+    pub(crate) en: String,
+    pub(crate) de: String,
+}
+
+impl LocalizedText {
+    /// Returns the text in the given language
+    pub(crate) fn get(&self, lng: Lang) -> &str {
+        match lng {
+            Lang::En => &self.en,
+            Lang::De => &self.de,
+        }
+    }
+}
+
+/// A condition which has to be fulfilled to complete an objective
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum Condition {
+    /// Every machine with one of the given ids is repaired
+    MachinesRepaired(Vec<ObjectId>),
+    /// The trade with the given id concluded while the objective was active
+    TradeConcluded(TradeId),
+    /// The given amount of seconds passed since the objective became active
+    Survive(u32),
+}
+
+/// What happens once an objective is completed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum Reward {
+    /// Sets the change rate of the given resources of the player
+    SetResourceChange(BTreeMap<ResourceKind, i16>),
+    /// Opens the given amount of breaches in the walls
+    OpenBreach(u8),
+    /// Unlocks every trade with the given id
+    UnlockTrade(TradeId),
+    /// Triggers the event with the given id
+    TriggerEvent(EventId),
+    /// The player wins the game
+    Win,
+}
+
+/// Describes one objective of the player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ObjectiveDefinition {
+    /// Stable identifier of the objective, used by follow-ups and saves
+    pub(crate) id: String,
+    pub(crate) title: LocalizedText,
+    /// The text shown in the handbook while the objective is active
+    #[serde(default)]
+    pub(crate) handbook: Option<LocalizedText>,
+    /// Conditions which all have to be fulfilled to complete the objective
+    #[serde(default)]
+    pub(crate) conditions: Vec<Condition>,
+    #[serde(default)]
+    pub(crate) rewards: Vec<Reward>,
+    /// Objectives which become active once this objective is completed
+    #[serde(default)]
+    pub(crate) follow_ups: Vec<String>,
+}
+
+/// Contains all objective definitions and where the player starts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ObjectiveGraph {
+    /// The objectives which are active at the start of the game
+    pub(crate) start: Vec<String>,
+    /// Contains the definitions of all objectives
+    pub(crate) objectives: Vec<ObjectiveDefinition>,
+}
+
+impl ObjectiveGraph {
+    /// Returns the definition of the objective with the given id
+    pub(crate) fn get(&self, id: &str) -> Option<&ObjectiveDefinition> {
+        self.objectives.iter().find(|objective| objective.id == id)
+    }
+}
+
+/// Returns the objective graph defined in `assets/objectives.yaml`
+pub(crate) fn objective_graph() -> &'static ObjectiveGraph {
+    static GRAPH: OnceLock<ObjectiveGraph> = OnceLock::new();
+    GRAPH.get_or_init(|| {
+        serde_yaml::from_str(include_str!("../../../assets/objectives.yaml"))
+            .expect("The objective definitions in assets/objectives.yaml are invalid")
+    })
+}
+
+/// An objective the player currently works on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ActiveObjective {
+    pub(crate) id: String,
+    /// The in-game time at which the objective became active
+    pub(crate) since: u32,
+    /// The trades that concluded while the objective was active, only trades the conditions of the objective wait for are kept
+    #[serde(default)]
+    pub(crate) concluded_trades: Vec<TradeId>,
+}
+
+/// The progress of the player in the objective graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ObjectiveProgress {
+    pub(crate) active: Vec<ActiveObjective>,
+    pub(crate) completed: Vec<String>,
+}
+
+impl Default for ObjectiveProgress {
+    /// The progress at the start of the game with the objectives of `assets/objectives.yaml`
+    fn default() -> Self {
+        Self::new(objective_graph())
+    }
+}
+
+impl ObjectiveProgress {
+    /// Creates the progress at the start of the game, where the start objectives of the graph are active
+    /// # Arguments
+    /// * `graph` - The objective graph of the game
+    pub(crate) fn new(graph: &ObjectiveGraph) -> Self {
+        Self {
+            active: graph
+                .start
+                .iter()
+                .map(|id| ActiveObjective {
+                    id: id.clone(),
+                    since: 0,
+                    concluded_trades: vec![],
+                })
+                .collect(),
+            completed: vec![],
+        }
+    }
+
+    /// Remembers a concluded trade for every active objective which has a condition waiting for it
+    /// # Arguments
+    /// * `id` - The id of the trade that concluded
+    /// * `graph` - The objective graph of the game
+    pub(crate) fn trade_concluded(&mut self, id: TradeId, graph: &ObjectiveGraph) {
+        for objective in &mut self.active {
+            let is_awaited = graph.get(&objective.id).is_some_and(|definition| {
+                definition
+                    .conditions
+                    .contains(&Condition::TradeConcluded(id))
+            });
+            if is_awaited && !objective.concluded_trades.contains(&id) {
+                objective.concluded_trades.push(id);
+            }
+        }
+    }
+
+    /// Returns the handbook text of every active objective, headed by its title
+    /// # Arguments
+    /// * `graph` - The objective graph of the game
+    /// * `lng` - The current language
+    pub(crate) fn handbook_text(&self, graph: &ObjectiveGraph, lng: Lang) -> Vec<String> {
+        self.active
+            .iter()
+            .filter_map(|objective| graph.get(&objective.id))
+            .filter_map(|objective| {
+                let text = objective.handbook.as_ref()?;
+                Some(format!("{}\n{}", objective.title.get(lng), text.get(lng)))
+            })
+            .collect()
+    }
+}
+
+impl GameState {
+    /// Completes every active objective whose conditions are all fulfilled.
+    /// The rewards of the completed objectives are applied and their follow-ups become active.
+    /// The game is saved as a milestone after an objective was completed.
+    /// # Arguments
+    /// * `force` - Completes the active objectives regardless of their conditions
+    /// # Returns
+    /// * `RLResult` - validates if the rewards were applied successfully
+    pub(crate) fn update_objectives(&mut self, force: bool) -> RLResult {
+        let (completed, active): (Vec<_>, Vec<_>) = self
+            .objectives
+            .active
+            .clone()
+            .into_iter()
+            .partition(|objective| force || self.is_objective_done(objective));
+        if completed.is_empty() {
+            return Ok(());
+        }
+        self.objectives.active = active;
+        for objective in completed {
+            let Some(definition) = self.objective_graph().get(&objective.id) else {
+                continue;
+            };
+            info!("Player completed objective {}", definition.id);
+            self.objectives.completed.push(definition.id.clone());
+            for reward in &definition.rewards {
+                self.apply_reward(reward)?;
+            }
+            for follow_up in &definition.follow_ups {
+                let known = self.objectives.completed.contains(follow_up)
+                    || self.objectives.active.iter().any(|o| &o.id == follow_up);
+                if !known {
+                    self.objectives.active.push(ActiveObjective {
+                        id: follow_up.clone(),
+                        since: self.player.time,
+                        concluded_trades: vec![],
+                    });
+                }
+            }
+        }
+        self.save(true)
+    }
+
+    /// Returns whether all conditions of the active objective are fulfilled
+    fn is_objective_done(&self, objective: &ActiveObjective) -> bool {
+        self.objective_progress(objective)
+            .iter()
+            .all(|(_, fulfilled)| *fulfilled)
+    }
+
+    /// Returns the progress of every condition of the active objective.
+    /// Conditions which concern several machines have one entry per machine.
+    /// # Arguments
+    /// * `objective` - The active objective
+    /// # Returns
+    /// * `Vec<(String, bool)>` - The description of each entry and whether it is fulfilled
+    pub(crate) fn objective_progress(&self, objective: &ActiveObjective) -> Vec<(String, bool)> {
+        let text = objective_tracker(self.lng);
+        let Some(definition) = self.objective_graph().get(&objective.id) else {
+            return vec![];
+        };
+        definition
+            .conditions
+            .iter()
+            .flat_map(|condition| match condition {
+                Condition::MachinesRepaired(ids) => ids
+                    .iter()
+                    .map(|id| {
+                        let repaired = self
+                            .machines
+                            .iter()
+                            .any(|machine| machine.id == *id && machine.state != State::Broken);
+                        let state = if repaired { text[3] } else { text[4] };
+                        (format!("{}: {state}", id.t(self.lng)), repaired)
+                    })
+                    .collect(),
+                Condition::TradeConcluded(id) => {
+                    let machine = self
+                        .machines
+                        .iter()
+                        .find(|machine| machine.trades.iter().any(|trade| trade.id == *id))
+                        .map_or(String::new(), |machine| {
+                            format!("{}: ", machine.id.t(self.lng))
+                        });
+                    vec![(
+                        format!("{machine}{}", id.t(self.lng)),
+                        objective.concluded_trades.contains(id),
+                    )]
+                }
+                Condition::Survive(seconds) => {
+                    let survived = self.player.time.saturating_sub(objective.since) / DESIRED_FPS;
+                    vec![(
+                        format!("{}: {}/{seconds}s", text[5], survived.min(*seconds)),
+                        survived >= *seconds,
+                    )]
+                }
+            })
+            .collect()
+    }
+
+    /// Draws the objective tracker, which lists the active objectives with the progress of their conditions.
+    /// If the tracker is collapsed only its title is drawn.
+    /// # Arguments
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    pub(crate) fn draw_objective_tracker(&self, canvas: &mut Canvas, scale: Vec2) {
+        let text = objective_tracker(self.lng);
+        let toggle = if self.tracker_collapsed {
+            text[1]
+        } else {
+            text[2]
+        };
+        let mut tracker = Text::new(
+            TextFragment::new(format!(
+                "{} ({}) - {toggle}\n",
+                text[0],
+                self.objectives.active.len()
+            ))
+            .color(Color::WHITE),
+        );
+        if !self.tracker_collapsed {
+            for objective in &self.objectives.active {
+                let Some(definition) = self.objective_graph().get(&objective.id) else {
+                    continue;
+                };
+                tracker.add(
+                    TextFragment::new(format!("{}\n", definition.title.get(self.lng)))
+                        .color(Color::WHITE),
+                );
+                for (description, fulfilled) in self.objective_progress(objective) {
+                    let (mark, color) = if fulfilled {
+                        ("\u{221a}", RLColor::GREEN)
+                    } else {
+                        ("\u{d7}", RLColor::RED)
+                    };
+                    tracker
+                        .add(TextFragment::new(format!("  {mark} {description}\n")).color(color));
+                }
+            }
+        }
+        tracker.set_scale(18.);
+        draw!(
+            canvas,
+            &tracker,
+            Vec2::new(TRACKER_POSITION.0, TRACKER_POSITION.1),
+            scale
+        );
+    }
+
+    /// Applies the reward of a completed objective
+    /// # Arguments
+    /// * `reward` - The reward to apply
+    /// # Returns
+    /// * `RLResult` - validates if the reward was applied successfully
+    fn apply_reward(&mut self, reward: &Reward) -> RLResult {
+        match reward {
+            Reward::SetResourceChange(change) => {
+                self.player.resources_change = self.player.resources_change.merged(change.clone());
+            }
+            Reward::OpenBreach(amount) => {
+                for _ in 0..*amount {
+                    self.spawn_breach(1);
+                }
+            }
+            Reward::UnlockTrade(id) => self.set_trade_locked(*id, false),
+            Reward::TriggerEvent(id) => self.trigger_event(*id)?,
+            // The endless mode has no win condition, the game goes on until the player dies
+            Reward::Win if self.mode == GameMode::Endless => {
+                info!("Objectives completed, the endless game goes on");
+            }
+            Reward::Win => {
+                info!("Player won the Game");
+                let lng = self.lng;
+                let cloned_sender = self.get_screen_sender()?.clone();
+                self.get_screen_sender()?
+                    .send(StackCommand::Screen(ScreenCommand::Push(Box::new(
+                        InfoScreen::new_winning_screen(cloned_sender, lng),
+                    ))))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_objective_graph() {
+        let graph = objective_graph();
+        for id in graph
+            .start
+            .iter()
+            .chain(graph.objectives.iter().flat_map(|o| &o.follow_ups))
+        {
+            assert!(graph.get(id).is_some(), "unknown objective {id}");
+        }
+        let progress = ObjectiveProgress::default();
+        assert_eq!(progress.active[0].id, "arrival");
+        assert!(progress.handbook_text(graph, Lang::En).is_empty());
+    }
+
+    #[test]
+    fn test_objective_progress() {
+        let mut gamestate = GameState::new_with_lang(Lang::En);
+        gamestate.start_scenario("crash_landing");
+        let objective = ActiveObjective {
+            id: "restore_life_support".to_string(),
+            since: 0,
+            concluded_trades: vec![],
+        };
+        assert_eq!(
+            gamestate.objective_progress(&objective),
+            vec![
+                ("Oxygen generator: broken".to_string(), false),
+                ("power generator: broken".to_string(), false),
+            ]
+        );
+        gamestate.machines[0].change_state_to(&State::Idle);
+        assert!(gamestate.objective_progress(&objective)[0].1);
+        assert!(!gamestate.is_objective_done(&objective));
+    }
+
+    #[test]
+    fn test_concluded_trades() {
+        let graph = objective_graph();
+        let mut progress = ObjectiveProgress::new(graph);
+        let objective = graph
+            .objectives
+            .iter()
+            .find(|objective| {
+                objective
+                    .conditions
+                    .contains(&Condition::TradeConcluded(TradeId::EmergencySignalOff))
+            })
+            .unwrap();
+        progress.active.push(ActiveObjective {
+            id: objective.id.clone(),
+            since: 0,
+            concluded_trades: vec![],
+        });
+        // Only trades a condition waits for are remembered, and only once
+        for id in [
+            TradeId::RepairHole,
+            TradeId::EmergencySignalOff,
+            TradeId::EmergencySignalOff,
+        ] {
+            progress.trade_concluded(id, graph);
+        }
+        assert!(progress.active[0].concluded_trades.is_empty());
+        assert_eq!(
+            progress.active[1].concluded_trades,
+            vec![TradeId::EmergencySignalOff]
+        );
+    }
+}
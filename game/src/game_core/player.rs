@@ -1,56 +1,92 @@
-use crate::backend::constants::DESIRED_FPS;
+use crate::backend::constants::{
+    CARRY_CAPACITY, DESIRED_FPS, MIN_CARRY_SPEED, MOVEMENT_SPEED, OVERWEIGHT_SLOWDOWN,
+    PLAYER_ICON_SIZE, START_ITEMS, START_RESOURCES, START_RESOURCES_CHANGE, SUIT_OXYGEN_MAX,
+};
 use crate::backend::rlcolor::RLColor;
 use crate::backend::screen::{Popup, ScreenCommand, StackCommand};
-use crate::game_core::item::Item;
-use crate::game_core::resources::Resources;
-use crate::languages::{game_info, petrol, printed_part, super_glue, Lang};
+use crate::game_core::item::{Item, ItemId};
+use crate::game_core::resources::{ResourceKind, Resources};
+use crate::game_core::status_effect::{StatusEffect, StatusKind};
+use crate::languages::{game_info, Lang};
 use crate::RLResult;
-use serde::{Deserialize, Serialize};
+use ggez::glam::Vec2;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::sync::mpsc::Sender;
-use tracing::info;
+use tracing::{info, warn};
 
 /// The current game player, containing its inventory and the current position, air and energy,
 /// along with their change rate
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Player {
     /// The current items of the player.
     pub(crate) inventory: Vec<(Item, i32)>,
-    pub(crate) position: (usize, usize),
-    /// The current air, energy and life of the player.
+    /// The position of the top left corner of the player (in px)
+    pub(crate) position: (f32, f32),
+    /// The current velocity of the player (in px per second)
+    #[serde(default)]
+    pub(crate) velocity: (f32, f32),
+    /// The direction the player is facing, it is the direction they moved in the last time
+    #[serde(default = "default_facing")]
+    pub(crate) facing: (f32, f32),
+    /// The current air, energy, life and needs of the player.
+    #[serde(deserialize_with = "deserialize_resources")]
     pub(crate) resources: Resources<u16>,
-    /// The current change rate of the air, energy and life of the player.
+    /// The current change rate of the air, energy, life and needs of the player.
+    #[serde(deserialize_with = "deserialize_resources_change")]
     pub(crate) resources_change: Resources<i16>,
-    /// The current milestone the player has reached.
-    pub milestone: usize,
     /// helper variable to check if the player lost life in the last tick
     pub(crate) last_damage: u32,
     /// contains the current ingame time
     pub(crate) time: u32,
+    /// Contains the status effects the player currently suffers from
+    #[serde(default)]
+    pub(crate) status_effects: Vec<StatusEffect>,
+    /// Contains the oxygen left in the suit of the player, it is only used outside of the station
+    #[serde(default = "full_suit")]
+    pub(crate) suit_oxygen: u16,
+}
+
+/// Returns the direction the player faces at the start of the game, which is down
+fn default_facing() -> (f32, f32) {
+    (0., 1.)
+}
+
+/// Returns the oxygen of a completely filled suit, used by saves without suit oxygen
+fn full_suit() -> u16 {
+    SUIT_OXYGEN_MAX
+}
+
+/// Reads the resources of the player from a save.
+/// Resources which did not exist when the game was saved are set to their start value.
+fn deserialize_resources<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Resources<u16>, D::Error> {
+    Ok(START_RESOURCES.merged(BTreeMap::deserialize(deserializer)?))
+}
+
+/// Reads the change rate of the player's resources from a save.
+/// Resources which did not exist when the game was saved get their start change rate.
+fn deserialize_resources_change<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Resources<i16>, D::Error> {
+    Ok(START_RESOURCES_CHANGE.merged(BTreeMap::deserialize(deserializer)?))
 }
 
 impl Player {
     pub fn new(lng: Lang) -> Self {
         info!("Default Player created");
         Self {
-            inventory: vec![
-                (Item::new(*super_glue(lng)), 0),
-                (Item::new(*petrol(lng)), 3),
-                (Item::new(*printed_part(lng)), 1),
-            ],
-            position: (600, 500),
-            resources: Resources {
-                oxygen: u16::MAX,
-                energy: u16::MAX,
-                life: u16::MAX,
-            },
-            resources_change: Resources {
-                oxygen: -5,
-                energy: -10,
-                life: 0,
-            },
-            milestone: 0,
+            inventory: inventory_from(START_ITEMS, lng),
+            position: (600., 500.),
+            velocity: (0., 0.),
+            facing: default_facing(),
+            resources: START_RESOURCES,
+            resources_change: START_RESOURCES_CHANGE,
             last_damage: 0,
             time: 0,
+            status_effects: vec![],
+            suit_oxygen: SUIT_OXYGEN_MAX,
         }
     }
     /// Checks whether the player has taken damage in the past few seconds and if not so start the regeneration
@@ -64,16 +100,16 @@ impl Player {
         lng: Lang,
     ) -> RLResult {
         match (
-            self.resources_change.life,
+            self.resources_change[ResourceKind::Life],
             self.last_damage,
-            self.resources.life,
+            self.resources[ResourceKind::Life],
         ) {
             // If Player has full life and is healing, stop healing, reset last damage
-            (change_life, _, u16::MAX) if change_life >= 0 => {
-                if self.resources_change.life > 0 {
+            (change_life, _, life) if change_life >= 0 && life >= ResourceKind::Life.info().max => {
+                if self.resources_change[ResourceKind::Life] > 0 {
                     info!("Player has full life, stopping healing");
                 }
-                self.resources_change.life = 0;
+                self.resources_change[ResourceKind::Life] = 0;
                 self.last_damage = 0;
             }
             // If player is healing reset last damage point
@@ -82,7 +118,7 @@ impl Player {
             }
             // If player does not take damage and 5 seconds have passed, start healing
             (0, last_damage, _) if last_damage >= 8 * DESIRED_FPS => {
-                self.resources_change.life += 5;
+                self.resources_change[ResourceKind::Life] += 5;
                 self.last_damage = 0;
                 let popup = Popup::new(RLColor::GREEN, game_info(lng)[0].to_string(), 5);
                 info!("Player started healing");
@@ -95,16 +131,141 @@ impl Player {
         }
         Ok(())
     }
-    /// changes the amount of an specific item in the inventory by a given number
+    /// Applies a status effect to the player.
+    /// If the player already suffers from it, it stacks up to its maximum and its duration is refreshed.
+    /// # Arguments
+    /// * `kind` - The kind of the effect
+    /// * `duration` - The duration of the effect in seconds
+    pub(crate) fn add_status(&mut self, kind: StatusKind, duration: u32) {
+        let new = StatusEffect::new(kind, duration);
+        if let Some(effect) = self.status_effects.iter_mut().find(|e| e.kind == kind) {
+            effect.stacks = (effect.stacks + 1).min(kind.max_stacks());
+            effect.remaining = effect.remaining.max(new.remaining);
+        } else {
+            info!("Player suffers from {:?}", kind);
+            self.status_effects.push(new);
+        }
+    }
+    /// Counts down the status effects, removes the ones that wore off and applies their life drain
+    pub(crate) fn update_status_effects(&mut self) {
+        self.status_effects.iter_mut().for_each(|effect| {
+            effect.remaining = effect.remaining.saturating_sub(1);
+        });
+        self.status_effects.retain(|effect| effect.remaining > 0);
+        let drain: i16 = self
+            .status_effects
+            .iter()
+            .map(|effect| effect.kind.life_drain() * i16::from(effect.stacks))
+            .sum();
+        if drain > 0 {
+            self.resources_change[ResourceKind::Life] = -drain;
+        } else if self.resources_change[ResourceKind::Life] < 0 {
+            self.resources_change[ResourceKind::Life] = 0;
+        }
+    }
+    /// Returns the highest movement speed of the player in px per second.
+    /// The strongest slowdown of all status effects and the weight the player carries applies.
+    pub(crate) fn movement_speed(&self) -> f32 {
+        let percent = self
+            .status_effects
+            .iter()
+            .map(|effect| effect.kind.speed_percent())
+            .min()
+            .unwrap_or(100)
+            .min(self.carry_speed_percent());
+        MOVEMENT_SPEED * percent as f32 / 100.
+    }
+    /// Returns the position of the feet of the player, they decide on which tile the player stands
+    pub(crate) fn feet(&self) -> Vec2 {
+        Vec2::new(
+            self.position.0 + PLAYER_ICON_SIZE.0 / 2.,
+            self.position.1 + PLAYER_ICON_SIZE.1 - 1.,
+        )
+    }
+    /// Returns the total weight of all items in the inventory
+    pub(crate) fn carry_weight(&self) -> u32 {
+        self.inventory
+            .iter()
+            .map(|(item, amount)| item.weight() * u32::try_from(*amount).unwrap_or(0))
+            .sum()
+    }
+    /// Returns the movement speed in percent the player has with the current carry weight
+    fn carry_speed_percent(&self) -> usize {
+        let overweight = self.carry_weight().saturating_sub(CARRY_CAPACITY);
+        let slowdown = usize::try_from(overweight).unwrap_or(usize::MAX);
+        100_usize
+            .saturating_sub(slowdown.saturating_mul(OVERWEIGHT_SLOWDOWN))
+            .max(MIN_CARRY_SPEED)
+    }
+    /// Returns the duration of trades in percent, the strongest slowdown of all status effects applies
+    pub(crate) fn trade_time_percent(&self) -> u32 {
+        self.status_effects
+            .iter()
+            .map(|effect| effect.kind.trade_time_percent())
+            .max()
+            .unwrap_or(100)
+    }
+    /// Adds every item the inventory is missing with an amount of zero.
+    /// Saves from before an item existed lack it in their inventory.
+    /// # Arguments
+    /// * `lng` - The language of the added items
+    pub(crate) fn add_missing_items(&mut self, lng: Lang) {
+        for (item, amount) in inventory_from([], lng) {
+            if !self.inventory.iter().any(|(i, _)| i.img == item.img) {
+                self.inventory.push((item, amount));
+            }
+        }
+    }
+    /// changes the amount of an specific item in the inventory by a given number.
+    /// The amount never goes below zero or above the stack limit of the item.
     /// # Arguments
     /// * `item` - The item to change the amount of
     /// * `amount_change` - The amount to change the item by
-    pub fn add_item(&mut self, item: &Item, amount_change: i32) {
-        self.inventory.iter_mut().for_each(|(i, amount)| {
-            if i.name == item.name {
-                *amount += amount_change;
-            }
-        });
+    /// # Returns
+    /// * `i32` - The part of the change that could not be applied, positive if the stack is full
+    pub fn add_item(&mut self, item: &Item, amount_change: i32) -> i32 {
+        let Some((_, amount)) = self.inventory.iter_mut().find(|(i, _)| i.name == item.name) else {
+            warn!("{} is not part of the inventory", item.name);
+            return amount_change;
+        };
+        let wanted = amount.saturating_add(amount_change);
+        let new_amount = wanted.clamp(0, item.stack_limit());
+        if new_amount != wanted {
+            warn!(
+                "Could not change the amount of {} from {} by {}",
+                item.name, amount, amount_change
+            );
+        }
+        *amount = new_amount;
+        wanted - new_amount
+    }
+    /// Returns the items the player can not take because their stack would exceed its limit
+    /// # Arguments
+    /// * `cost` - The items and their amount, negative amounts are received by the player
+    /// # Returns
+    /// * `Vec<(Item, i32)>` - Every item that does not fit together with the amount that does not fit
+    pub(crate) fn overflowing_items(&self, cost: &[(Item, i32)]) -> Vec<(Item, i32)> {
+        cost.iter()
+            .map(|(item, amount)| {
+                let overflow = self.get_item_amount(item) - amount - item.stack_limit();
+                (item, overflow)
+            })
+            .filter(|(_, overflow)| *overflow > 0)
+            .map(|(item, overflow)| (item.clone(), overflow))
+            .collect()
+    }
+
+    /// Returns the items the player is missing to pay the given cost, for example the cost of a trade or a blueprint
+    /// # Arguments
+    /// * `cost` - The items and their amount, positive amounts have to be paid by the player
+    /// # Returns
+    /// * `Vec<(Item, i32)>` - Every item that is missing together with the missing amount
+    pub(crate) fn missing_items(&self, cost: &[(Item, i32)]) -> Vec<(Item, i32)> {
+        cost.iter()
+            .map(|(item, demand)| (item, demand - self.get_item_amount(item)))
+            .filter(|(_, missing)| *missing > 0)
+            .map(|(item, missing)| (item.clone(), missing))
+            .collect()
     }
     /// returns the amount of an specific item in the inventory
     /// # Arguments
@@ -128,17 +289,38 @@ impl Player {
 /// * `super_glue` - The amount of super glue
 /// * `benzin` - The amount of benzin
 /// * `gedrucktesteil` - The amount of the printed part
+/// * `scrap` - The amount of scrap
 pub fn gen_inventory(
     super_glue_amount: i32,
     petrol_amount: i32,
     printed_parts_amount: i32,
+    scrap_amount: i32,
     lng: Lang,
 ) -> Vec<(Item, i32)> {
-    vec![
-        (Item::new(*super_glue(lng)), super_glue_amount),
-        (Item::new(*petrol(lng)), petrol_amount),
-        (Item::new(*printed_part(lng)), printed_parts_amount),
-    ]
+    inventory_from(
+        ItemId::ALL.into_iter().zip([
+            super_glue_amount,
+            petrol_amount,
+            printed_parts_amount,
+            scrap_amount,
+        ]),
+        lng,
+    )
+}
+
+/// Returns an inventory with every item, items without an amount are set to zero
+/// # Arguments
+/// * `amounts` - The amount of the items, if an item occurs more than once its last amount is used
+/// * `lng` - The language of the items
+pub(crate) fn inventory_from(
+    amounts: impl IntoIterator<Item = (ItemId, i32)>,
+    lng: Lang,
+) -> Vec<(Item, i32)> {
+    let amounts = amounts.into_iter().collect::<BTreeMap<_, _>>();
+    ItemId::ALL
+        .into_iter()
+        .map(|id| (id.item(lng), amounts.get(&id).copied().unwrap_or(0)))
+        .collect()
 }
 
 #[cfg(test)]
@@ -158,13 +340,13 @@ mod test {
     fn test_case_one_life_regeneration() {
         let (mut gamestate, _) = setup_gamestate();
         let mut player = Player::new(Lang::De);
-        player.resources.life = u16::MAX;
-        player.resources_change.life = 5;
+        player.resources[ResourceKind::Life] = u16::MAX;
+        player.resources_change[ResourceKind::Life] = 5;
         player.last_damage = 1000;
         player
             .life_regeneration(&gamestate.get_screen_sender().unwrap().clone(), Lang::De)
             .unwrap();
-        assert_eq!(player.resources_change.life, 0);
+        assert_eq!(player.resources_change[ResourceKind::Life], 0);
         assert_eq!(player.last_damage, 0);
     }
 
@@ -172,8 +354,8 @@ mod test {
     fn test_case_two_life_regeneration() {
         let (mut gamestate, _) = setup_gamestate();
         let mut player = Player::new(Lang::De);
-        player.resources.life = 1000;
-        player.resources_change.life = 5;
+        player.resources[ResourceKind::Life] = 1000;
+        player.resources_change[ResourceKind::Life] = 5;
         player.last_damage = 1000;
         player
             .life_regeneration(&gamestate.get_screen_sender().unwrap().clone(), Lang::De)
@@ -185,13 +367,13 @@ mod test {
     fn test_case_three_life_regeneration() {
         let (mut gamestate, _receiver) = setup_gamestate();
         let mut player = Player::new(Lang::De);
-        player.resources.life = 1000;
-        player.resources_change.life = 0;
+        player.resources[ResourceKind::Life] = 1000;
+        player.resources_change[ResourceKind::Life] = 0;
         player.last_damage = 900;
         player
             .life_regeneration(&gamestate.get_screen_sender().unwrap().clone(), Lang::De)
             .unwrap();
-        assert_eq!(player.resources_change.life, 5);
+        assert_eq!(player.resources_change[ResourceKind::Life], 5);
         assert_eq!(player.last_damage, 0);
     }
 
@@ -199,13 +381,13 @@ mod test {
     fn test_case_four_life_regeneration() {
         let (mut gamestate, _) = setup_gamestate();
         let mut player = Player::new(Lang::De);
-        player.resources.life = 20000;
+        player.resources[ResourceKind::Life] = 20000;
         player.last_damage = 400;
-        player.resources_change.life = 0;
+        player.resources_change[ResourceKind::Life] = 0;
         player
             .life_regeneration(&gamestate.get_screen_sender().unwrap().clone(), Lang::De)
             .unwrap();
-        assert_eq!(player.resources_change.life, 0);
+        assert_eq!(player.resources_change[ResourceKind::Life], 0);
         assert_eq!(player.last_damage, 401);
     }
 
@@ -216,16 +398,55 @@ mod test {
         gamestate.set_sender(channel.0);
         let mut player = Player {
             last_damage: 3,
-            resources_change: Resources {
-                life: -1,
-                ..Default::default()
-            },
+            resources_change: Resources::default().with(ResourceKind::Life, -1),
             ..Player::new(Lang::De)
         };
         player
             .life_regeneration(&gamestate.get_screen_sender().unwrap().clone(), Lang::De)
             .unwrap();
-        assert_eq!(player.resources_change.life, -1);
+        assert_eq!(player.resources_change[ResourceKind::Life], -1);
         assert_eq!(player.last_damage, 0);
     }
+
+    #[test]
+    fn test_status_effects_stack() {
+        let mut player = Player::new(Lang::De);
+        for _ in 0..5 {
+            player.add_status(StatusKind::Injury, 1);
+            player.add_status(StatusKind::Hypoxia, 1);
+        }
+        assert_eq!(player.status_effects.len(), 2);
+        assert_eq!(player.status_effects[0].stacks, 3);
+        assert_eq!(player.status_effects[1].stacks, 1);
+        player.update_status_effects();
+        assert_eq!(player.resources_change[ResourceKind::Life], -56);
+        assert!(player.movement_speed() < MOVEMENT_SPEED);
+        for _ in 0..DESIRED_FPS {
+            player.update_status_effects();
+        }
+        assert!(player.status_effects.is_empty());
+        assert_eq!(player.resources_change[ResourceKind::Life], 0);
+        assert!((player.movement_speed() - MOVEMENT_SPEED).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_inventory_limits() {
+        let mut player = Player::new(Lang::De);
+        let glue = ItemId::SuperGlue.item(Lang::De);
+        let limit = glue.stack_limit();
+        assert_eq!(player.add_item(&glue, limit + 3), 3);
+        assert_eq!(player.get_item_amount(&glue), limit);
+        assert_eq!(
+            player.overflowing_items(&[(glue.clone(), -1)]),
+            vec![(glue.clone(), 1)]
+        );
+        assert_eq!(player.add_item(&glue, -limit - 2), -2);
+        assert_eq!(player.get_item_amount(&glue), 0);
+        assert!(player.inventory.iter().all(|(_, amount)| *amount >= 0));
+        // carrying too much slows the player down
+        let speed = player.movement_speed();
+        player.add_item(&ItemId::Petrol.item(Lang::De), 5);
+        assert!(player.carry_weight() > CARRY_CAPACITY);
+        assert!(player.movement_speed() < speed);
+    }
 }
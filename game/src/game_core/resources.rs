@@ -1,80 +1,252 @@
+use crate::backend::constants::RESOURCE_REGISTRY;
 use crate::game_core::infoscreen::DeathReason;
+use crate::game_core::status_effect::StatusKind;
+use crate::languages::{resource_name, Lang};
+use ggez::graphics::Color;
 use serde::{Deserialize, Serialize};
-use std::cmp::PartialOrd;
+use std::collections::BTreeMap;
 use std::ops;
 
+/// Contains the amount of different resources
+pub(crate) const RESOURCE_COUNT: usize = 6;
+
+/// The kind of a resource, used as key of `Resources`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceKind {
+    Oxygen = 0,
+    Energy = 1,
+    Life = 2,
+    Food = 3,
+    Water = 4,
+    /// Unlike the other resources fatigue rises over time and the player has to rest to lower it
+    Fatigue = 5,
+}
+
+impl ResourceKind {
+    /// Contains every kind of resource in the order they are stored in `Resources`
+    pub(crate) const ALL: [ResourceKind; RESOURCE_COUNT] = [
+        ResourceKind::Oxygen,
+        ResourceKind::Energy,
+        ResourceKind::Life,
+        ResourceKind::Food,
+        ResourceKind::Water,
+        ResourceKind::Fatigue,
+    ];
+
+    pub fn t(self, lng: Lang) -> &'static str {
+        resource_name(lng)[self as usize]
+    }
+
+    /// Returns the entry of the resource in the registry
+    pub(crate) fn info(self) -> &'static ResourceInfo {
+        &RESOURCE_REGISTRY[self as usize]
+    }
+
+    /// Returns whether the given amount of the resource is critical for the player
+    /// # Arguments
+    /// * `amount` - The amount of the resource
+    pub(crate) fn is_critical(self, amount: u16) -> bool {
+        match self.info().critical {
+            Critical::Empty => amount == 0,
+            Critical::Full => amount >= self.info().max,
+        }
+    }
+}
+
+/// Defines when a resource becomes critical for the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Critical {
+    /// The resource is critical once it is used up
+    Empty,
+    /// The resource is critical once it reached its maximum, used for resources which rise over time
+    Full,
+}
+
+/// An entry of the resource registry, which describes how a resource behaves and how it is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ResourceInfo {
+    /// The highest amount of the resource, the amount is clamped to it
+    pub(crate) max: u16,
+    /// Defines when the resource becomes critical
+    pub(crate) critical: Critical,
+    /// The status effect the player suffers from while the resource is critical
+    pub(crate) status: Option<StatusKind>,
+    /// The reason of death if the player dies while the resource is critical
+    pub(crate) death_reason: Option<DeathReason>,
+    /// Needs of the player keep changing during the whole game and are drawn outside of the station
+    pub(crate) need: bool,
+    /// The position of the bar of the resource
+    pub(crate) position: (f32, f32),
+    /// The color of the bar of the resource
+    pub(crate) color: Color,
+}
+
 /// This struct holds data for resources
 /// This is used to describe the current state and change rate of the player's resources
 /// Use type `i16` for the resource change rate
 /// Use type `u16` for the resource amount
+/// Every resource can be accessed by indexing with its `ResourceKind`.
+/// Resources are saved by their name, resources which are missing in a save or definition are set to their default.
 #[derive(Copy, Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Resources<T: PartialOrd> {
-    pub(crate) oxygen: T,
-    pub(crate) energy: T,
-    pub(crate) life: T,
+#[serde(
+    from = "BTreeMap<ResourceKind, T>",
+    into = "BTreeMap<ResourceKind, T>",
+    bound(
+        serialize = "T: Serialize + Copy",
+        deserialize = "T: Deserialize<'de> + Default + Copy"
+    )
+)]
+pub struct Resources<T>([T; RESOURCE_COUNT]);
+
+impl<T: Copy> Resources<T> {
+    /// Creates new `Resources` with the same value for every resource
+    /// # Arguments
+    /// * `value` - The value of every resource
+    pub(crate) const fn filled(value: T) -> Self {
+        Self([value; RESOURCE_COUNT])
+    }
+
+    /// Returns the resources with the given resource set to a new value
+    /// # Arguments
+    /// * `kind` - The kind of the resource
+    /// * `value` - The new value of the resource
+    pub(crate) const fn with(mut self, kind: ResourceKind, value: T) -> Self {
+        self.0[kind as usize] = value;
+        self
+    }
+
+    /// Returns these resources with every resource contained in the map set to its value from the map
+    /// # Arguments
+    /// * `map` - The resources which are overridden
+    pub(crate) fn merged(self, map: BTreeMap<ResourceKind, T>) -> Self {
+        map.into_iter()
+            .fold(self, |resources, (kind, value)| resources.with(kind, value))
+    }
+
+    /// Returns every resource together with its kind
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (ResourceKind, T)> + '_ {
+        ResourceKind::ALL.into_iter().zip(self.0)
+    }
+}
+
+impl<T> ops::Index<ResourceKind> for Resources<T> {
+    type Output = T;
+    fn index(&self, kind: ResourceKind) -> &T {
+        &self.0[kind as usize]
+    }
+}
+
+impl<T> ops::IndexMut<ResourceKind> for Resources<T> {
+    fn index_mut(&mut self, kind: ResourceKind) -> &mut T {
+        &mut self.0[kind as usize]
+    }
+}
+
+/// Define how resources are read from a save or definition
+impl<T: Default + Copy> From<BTreeMap<ResourceKind, T>> for Resources<T> {
+    fn from(map: BTreeMap<ResourceKind, T>) -> Self {
+        Self::default().merged(map)
+    }
+}
+
+/// Define how resources are written to a save
+impl<T: Copy> From<Resources<T>> for BTreeMap<ResourceKind, T> {
+    fn from(resources: Resources<T>) -> Self {
+        resources.iter().collect()
+    }
 }
 
 /// Define how struct will be created from an iterator
-impl FromIterator<u16> for Resources<u16> {
-    fn from_iter<I: IntoIterator<Item = u16>>(iter: I) -> Self {
+/// The values are assigned in the order of `ResourceKind::ALL`, missing values are set to their default
+impl<T: Default> FromIterator<T> for Resources<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut iter = iter.into_iter();
-        Self {
-            oxygen: iter.next().unwrap(),
-            energy: iter.next().unwrap(),
-            life: iter.next().unwrap(),
-        }
+        Self(std::array::from_fn(|_| iter.next().unwrap_or_default()))
     }
 }
 
 /// Define how resources can be compared
-impl<T: PartialOrd> IntoIterator for Resources<T> {
+impl<T> IntoIterator for Resources<T> {
     type Item = T;
-    type IntoIter = std::array::IntoIter<T, 3>;
+    type IntoIter = std::array::IntoIter<T, RESOURCE_COUNT>;
 
     fn into_iter(self) -> Self::IntoIter {
-        [self.oxygen, self.energy, self.life].into_iter()
+        self.0.into_iter()
     }
 }
 
 /// Implement the `Add` trait for `Resources`
 /// Use '+' to add two `Resources` of the same type
-impl<T: ops::Add<Output = T> + PartialOrd> ops::Add<Resources<T>> for Resources<T> {
+impl<T: ops::Add<Output = T> + Copy> ops::Add<Resources<T>> for Resources<T> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
-        Self {
-            oxygen: self.oxygen + rhs.oxygen,
-            energy: self.energy + rhs.energy,
-            life: self.life + rhs.life,
-        }
+        Self(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
     }
 }
 
 /// Implement the `Sub` trait for `Resources`
 /// Use '-' to substract two `Resources` of the same type from each other
-impl<T: ops::Sub<Output = T> + PartialOrd> ops::Sub<Resources<T>> for Resources<T> {
+impl<T: ops::Sub<Output = T> + Copy> ops::Sub<Resources<T>> for Resources<T> {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
-        Self {
-            oxygen: self.oxygen - rhs.oxygen,
-            energy: self.energy - rhs.energy,
-            life: self.life - rhs.life,
-        }
+        Self(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+/// Implement the `Mul` trait for `Resources`
+/// Use '*' to multiply every resource with the same factor
+impl<T: ops::Mul<Output = T> + Copy> ops::Mul<T> for Resources<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Self(self.0.map(|value| value * rhs))
     }
 }
 
-/// Implement function to check on death reason for `Resources` of type `u16`
+/// Implement the amount specific functions for `Resources` of type `u16`
 impl Resources<u16> {
-    /// This function returns the value that reached zero first
-    /// If no value reached zero, it returns None
+    /// Returns the resources with every amount clamped to the maximum of its resource
+    pub(crate) fn clamped(self) -> Self {
+        Self(std::array::from_fn(|i| {
+            self.0[i].min(ResourceKind::ALL[i].info().max)
+        }))
+    }
+
+    /// Applies a change rate to the amounts without over- or underflowing.
+    /// The amounts are clamped to the maximum of their resource.
+    /// # Arguments
+    /// * `change` - The change of every resource
+    pub(crate) fn saturating_add_signed(self, change: Resources<i16>) -> Self {
+        Self(std::array::from_fn(|i| {
+            self.0[i].saturating_add_signed(change.0[i])
+        }))
+        .clamped()
+    }
+
+    /// Returns the amount of the resource relative to its maximum, between 0 and 1
+    /// # Arguments
+    /// * `kind` - The kind of the resource
+    pub(crate) fn fraction(&self, kind: ResourceKind) -> f32 {
+        f32::from(self[kind]) / f32::from(kind.info().max)
+    }
+
+    /// Returns every resource which is critical for the player
+    pub(crate) fn critical(&self) -> impl Iterator<Item = ResourceKind> + '_ {
+        self.iter()
+            .filter(|(kind, amount)| kind.is_critical(*amount))
+            .map(|(kind, _)| kind)
+    }
+
+    /// Returns the reason of death of the first critical resource which has one,
+    /// running out of oxygen and energy at the same time is one combined reason
+    /// If no such resource is critical, it returns None
     pub fn get_death_reason(self) -> Option<DeathReason> {
-        if self.oxygen == 0 && self.energy == 0 {
+        let mut reasons = self.critical().filter_map(|kind| kind.info().death_reason);
+        let first = reasons.next()?;
+        if first == DeathReason::Oxygen && reasons.any(|reason| reason == DeathReason::Energy) {
             Some(DeathReason::Both)
-        } else if self.oxygen == 0 {
-            Some(DeathReason::Oxygen)
-        } else if self.energy == 0 {
-            Some(DeathReason::Energy)
         } else {
-            None
+            Some(first)
         }
     }
 }
@@ -82,60 +254,86 @@ impl Resources<u16> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use ResourceKind::{Energy, Fatigue, Food, Life, Oxygen};
 
     #[test]
     fn addition() {
-        let a = Resources {
-            oxygen: 1,
-            energy: 2,
-            life: 3,
-        };
-        let b = Resources {
-            oxygen: 4,
-            energy: 5,
-            life: 6,
-        };
+        let a = Resources::filled(0)
+            .with(Oxygen, 1)
+            .with(Energy, 2)
+            .with(Life, 3);
+        let b = Resources::filled(0)
+            .with(Oxygen, 4)
+            .with(Energy, 5)
+            .with(Life, 6);
         let add_result = a + b;
-        let add_control = Resources {
-            oxygen: 5,
-            energy: 7,
-            life: 9,
-        };
+        let add_control = Resources::filled(0)
+            .with(Oxygen, 5)
+            .with(Energy, 7)
+            .with(Life, 9);
         assert_eq!(add_result, add_control);
     }
 
     #[test]
     fn subtraction() {
-        let a = Resources {
-            oxygen: 1,
-            energy: 2,
-            life: 3,
-        };
-        let b = Resources {
-            oxygen: 4,
-            energy: 5,
-            life: 6,
-        };
+        let a = Resources::filled(1).with(Energy, 2).with(Life, 3);
+        let b = Resources::filled(4).with(Energy, 5).with(Life, 6);
         let sub_result = a - b;
-        let sub_control = Resources {
-            oxygen: -3,
-            energy: -3,
-            life: -3,
-        };
-        assert_eq!(sub_result, sub_control);
+        assert_eq!(sub_result, Resources::filled(-3));
+    }
+
+    #[test]
+    fn multiplication() {
+        let a = Resources::filled(0).with(Oxygen, -15).with(Energy, -5);
+        let mul_control = Resources::filled(0).with(Oxygen, -45).with(Energy, -15);
+        assert_eq!(a * 3, mul_control);
     }
 
     #[test]
     fn into_it() {
-        let a = Resources {
-            oxygen: 3,
-            energy: 2,
-            life: 1,
-        };
+        let a = Resources::filled(0)
+            .with(Oxygen, 3)
+            .with(Energy, 2)
+            .with(Life, 1);
         let mut ait = a.into_iter();
         assert_eq!(ait.next().unwrap(), 3);
         assert_eq!(ait.next().unwrap(), 2);
         assert_eq!(ait.next().unwrap(), 1);
+        assert_eq!(ait.nth(RESOURCE_COUNT - 4), Some(0));
         assert_eq!(Some(ait.next()), Some(None));
     }
+
+    #[test]
+    fn saturating_and_clamping() {
+        let amounts = Resources::filled(10).with(Fatigue, u16::MAX - 1);
+        let change = Resources::filled(-20).with(Fatigue, 5);
+        let result = amounts.saturating_add_signed(change);
+        assert_eq!(result[Oxygen], 0);
+        assert_eq!(result[Fatigue], Fatigue.info().max);
+        assert_eq!(result.critical().collect::<Vec<_>>(), ResourceKind::ALL);
+        assert_eq!(result.get_death_reason(), Some(DeathReason::Both));
+        assert_eq!(
+            Resources::filled(1).with(Oxygen, 0).get_death_reason(),
+            Some(DeathReason::Oxygen)
+        );
+        assert_eq!(
+            Resources::filled(1).with(Energy, 0).get_death_reason(),
+            Some(DeathReason::Energy)
+        );
+        assert_eq!(Resources::filled(1).get_death_reason(), None);
+    }
+
+    #[test]
+    fn missing_resources_are_default() {
+        let resources: Resources<i16> = serde_yaml::from_str("oxygen: -10\nfood: 4").unwrap();
+        assert_eq!(
+            resources,
+            Resources::filled(0).with(Oxygen, -10).with(Food, 4)
+        );
+        let saved = serde_yaml::to_string(&resources).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<Resources<i16>>(&saved).unwrap(),
+            resources
+        );
+    }
 }
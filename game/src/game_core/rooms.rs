@@ -0,0 +1,271 @@
+//! This File contains the air pressure of the rooms and the doors between them
+use crate::backend::constants::{
+    AIRLOCK_LEAK, CONDITION_STATUS_DURATION, ROOM_INFO_POSITION, ROOM_PRESSURE_LEAK,
+    ROOM_PRESSURE_MAX, ROOM_PRESSURE_REFILL,
+};
+use crate::backend::gamestate::GameState;
+use crate::backend::rlcolor::RLColor;
+use crate::backend::utils::{get_draw_params, is_colliding};
+use crate::game_core::map::Door;
+use crate::game_core::status_effect::StatusKind;
+use crate::languages::room_pressure;
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawMode, Mesh, MeshBuilder, Rect, Text, TextFragment};
+use ggez::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tracing::info;
+
+/// The state of the rooms and doors of the station
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RoomState {
+    /// The indices of the doors which are open, all other doors are closed
+    #[serde(default)]
+    pub(crate) open_doors: BTreeSet<usize>,
+    /// The air pressure of every room, rooms without an entry have full pressure
+    #[serde(default)]
+    pub(crate) pressure: Vec<u16>,
+}
+
+impl GameState {
+    /// Returns the air pressure of the room with the given index
+    pub(crate) fn room_pressure(&self, room: usize) -> u16 {
+        self.rooms
+            .pressure
+            .get(room)
+            .copied()
+            .unwrap_or(ROOM_PRESSURE_MAX)
+    }
+
+    /// Returns the index of the room the player is standing in, their feet decide in which room they stand
+    pub(crate) fn player_room(&self) -> Option<usize> {
+        self.map().room_at(self.player.feet())
+    }
+
+    /// Returns all doors which are closed right now
+    pub(crate) fn closed_doors(&self) -> impl Iterator<Item = &'static Door> + '_ {
+        self.map()
+            .doors
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.rooms.open_doors.contains(index))
+            .map(|(_, door)| door)
+    }
+
+    /// Returns all airlocks which are open right now
+    fn open_airlocks(&self) -> impl Iterator<Item = &'static Door> + '_ {
+        self.rooms
+            .open_doors
+            .iter()
+            .filter_map(|&index| self.map().doors.get(index))
+            .filter(|door| door.airlock)
+    }
+
+    /// Opens or closes the door with the given index.
+    /// A door can not be closed while the player is standing in it.
+    /// # Arguments
+    /// * `door` - The index of the door
+    pub(crate) fn toggle_door(&mut self, door: usize) {
+        let Some(definition) = self.map().doors.get(door) else {
+            return;
+        };
+        if self.rooms.open_doors.remove(&door) {
+            if is_colliding(self.player.position, &definition.area) {
+                self.rooms.open_doors.insert(door);
+                return;
+            }
+            info!("Closing door {}", door);
+        } else {
+            info!("Opening door {}", door);
+            self.rooms.open_doors.insert(door);
+        }
+        self.update_collision();
+    }
+
+    /// Returns the rooms grouped by the zones they form, rooms connected by open doors share their air
+    fn room_zones(&self) -> Vec<Vec<usize>> {
+        let mut zones: Vec<Vec<usize>> =
+            (0..self.map().rooms.len()).map(|room| vec![room]).collect();
+        for door in self
+            .rooms
+            .open_doors
+            .iter()
+            .filter_map(|&index| self.map().doors.get(index))
+        {
+            let mut merged = vec![];
+            zones.retain(|zone| {
+                let connected = zone.iter().any(|room| door.rooms.contains(room));
+                if connected {
+                    merged.extend(zone);
+                }
+                !connected
+            });
+            zones.push(merged);
+        }
+        zones
+    }
+
+    /// Updates the air pressure of all rooms.
+    /// Rooms connected by open doors share their air, hull breaches and open airlocks let the air of their zone escape
+    /// and zones without leaks are refilled slowly. Standing in a room without air causes hypoxia.
+    pub(crate) fn update_rooms(&mut self) {
+        let map = self.map();
+        if map.rooms.is_empty() {
+            return;
+        }
+        let mut pressure = (0..map.rooms.len())
+            .map(|room| self.room_pressure(room))
+            .collect::<Vec<_>>();
+        for zone in self.room_zones() {
+            let leak = self
+                .machines
+                .iter()
+                .filter_map(|machine| Some((machine.breach?, map.room_in(&machine.hitbox)?)))
+                .filter(|(_, room)| zone.contains(room))
+                .map(|(breach, _)| u16::from(breach.severity) * ROOM_PRESSURE_LEAK)
+                .sum::<u16>()
+                + self
+                    .open_airlocks()
+                    .filter(|door| door.rooms.iter().any(|room| zone.contains(room)))
+                    .map(|_| AIRLOCK_LEAK)
+                    .sum::<u16>();
+            // the air spreads evenly in the zone
+            let total = zone
+                .iter()
+                .map(|&room| u32::from(pressure[room]))
+                .sum::<u32>();
+            let average = u16::try_from(total / zone.len() as u32).unwrap_or(ROOM_PRESSURE_MAX);
+            let next = if leak > 0 {
+                average.saturating_sub(leak)
+            } else {
+                (average + ROOM_PRESSURE_REFILL).min(ROOM_PRESSURE_MAX)
+            };
+            for &room in &zone {
+                pressure[room] = next;
+            }
+        }
+        self.rooms.pressure = pressure;
+        if self
+            .player_room()
+            .is_some_and(|room| self.room_pressure(room) == 0)
+        {
+            self.player
+                .add_status(StatusKind::Hypoxia, CONDITION_STATUS_DURATION);
+        }
+    }
+
+    /// Draws the rooms with a red tint the lower their pressure is, the doors
+    /// and the pressure of the room the player is standing in
+    /// # Arguments
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// * `RLResult` - validates if the meshes were created successfully
+    pub(crate) fn draw_rooms(&self, canvas: &mut Canvas, scale: Vec2, ctx: &Context) -> RLResult {
+        let map = self.map();
+        if map.rooms.is_empty() && map.doors.is_empty() {
+            return Ok(());
+        }
+        let mut builder = MeshBuilder::new();
+        let mut empty = true;
+        for (row, tiles) in map.tiles.iter().enumerate() {
+            for column in 0..tiles.len() {
+                let Some(room) = map.room(column, row) else {
+                    continue;
+                };
+                let missing =
+                    1. - f32::from(self.room_pressure(room)) / f32::from(ROOM_PRESSURE_MAX);
+                if missing <= 0. {
+                    continue;
+                }
+                builder.rectangle(
+                    DrawMode::fill(),
+                    Rect::new(
+                        column as f32 * map.tile_size,
+                        row as f32 * map.tile_size,
+                        map.tile_size,
+                        map.tile_size,
+                    ),
+                    Color::new(1., 0., 0., missing * 0.4),
+                )?;
+                empty = false;
+            }
+        }
+        for (index, door) in map.doors.iter().enumerate() {
+            let mode = if self.rooms.open_doors.contains(&index) {
+                DrawMode::stroke(3.)
+            } else {
+                DrawMode::fill()
+            };
+            builder.rectangle(mode, door.area, RLColor::DARK_RED)?;
+            empty = false;
+        }
+        if !empty {
+            let mesh = Mesh::from_data(ctx, builder.build());
+            draw!(canvas, &mesh, Vec2::ZERO, scale);
+        }
+
+        if let Some(room) = self.player_room() {
+            let percent = u32::from(self.room_pressure(room)) * 100 / u32::from(ROOM_PRESSURE_MAX);
+            let mut text = Text::new(
+                TextFragment::new(format!(
+                    "{}: {} {percent}%",
+                    map.rooms[room].name.get(self.lng),
+                    room_pressure(self.lng)
+                ))
+                .color(Color::WHITE),
+            );
+            text.set_scale(22.);
+            draw!(
+                canvas,
+                &text,
+                Vec2::new(ROOM_INFO_POSITION.0, ROOM_INFO_POSITION.1),
+                scale
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::constants::{gen_machine, ObjectId};
+    use crate::languages::Lang;
+    use crate::machines::breach::Breach;
+
+    #[test]
+    fn test_room_pressure() {
+        let mut gamestate = GameState::new_with_lang(Lang::En);
+        gamestate.start_scenario("outpost");
+        let map = gamestate.map();
+        let habitat = map.room_at(Vec2::new(500., 500.)).unwrap();
+        let workshop = map.room_at(Vec2::new(1200., 500.)).unwrap();
+        // open a breach in the habitat
+        let position = map.breach_positions[0];
+        let mut breach = gen_machine(ObjectId::Hole, position, Lang::En);
+        breach.breach = Some(Breach::new(1));
+        assert_eq!(map.room_in(&breach.hitbox), Some(habitat));
+        gamestate.machines.push(breach);
+        for _ in 0..10 {
+            gamestate.update_rooms();
+        }
+        assert!(gamestate.room_pressure(habitat) < ROOM_PRESSURE_MAX);
+        // the closed door keeps the air in the workshop
+        assert_eq!(gamestate.room_pressure(workshop), ROOM_PRESSURE_MAX);
+        let door = map
+            .doors
+            .iter()
+            .position(|door| door.rooms.contains(&habitat) && door.rooms.contains(&workshop))
+            .unwrap();
+        gamestate.toggle_door(door);
+        gamestate.update_rooms();
+        assert!(gamestate.room_pressure(workshop) < ROOM_PRESSURE_MAX);
+        assert_eq!(
+            gamestate.room_pressure(workshop),
+            gamestate.room_pressure(habitat)
+        );
+    }
+}
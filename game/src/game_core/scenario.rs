@@ -0,0 +1,236 @@
+//! This File contains the scenarios, which bundle everything a game starts with
+use crate::backend::constants::{
+    gen_machine, ObjectId, START_ITEMS, START_RESOURCES, START_RESOURCES_CHANGE, SUIT_OXYGEN_MAX,
+};
+use crate::backend::gamestate::GameState;
+use crate::game_core::eva::EvaState;
+use crate::game_core::item::ItemId;
+use crate::game_core::map::map;
+use crate::game_core::objective::{
+    objective_graph, LocalizedText, ObjectiveGraph, ObjectiveProgress,
+};
+use crate::game_core::player::inventory_from;
+use crate::game_core::resources::ResourceKind;
+use crate::game_core::rooms::RoomState;
+use crate::game_core::scheduler::{event_table, EventTable};
+use crate::game_core::tutorial::{TutorialProgress, TutorialStep};
+use crate::machines::machine::State;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use tracing::info;
+
+/// A machine of a scenario which differs from the machines placed on the map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ScenarioMachine {
+    pub(crate) id: ObjectId,
+    /// The position of the top left corner of the machine.
+    /// Machines with a position are added to the station, without one they change the machine placed on the map.
+    #[serde(default)]
+    pub(crate) position: Option<(f32, f32)>,
+    /// The state the machine starts in
+    #[serde(default)]
+    pub(crate) state: State,
+}
+
+/// Describes one scenario with the station, the supplies, the events and the objectives of the game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Scenario {
+    /// Stable identifier of the scenario, used by saves
+    pub(crate) id: String,
+    pub(crate) name: LocalizedText,
+    pub(crate) description: LocalizedText,
+    /// The id of the map of the station
+    #[serde(default = "default_map")]
+    pub(crate) map: String,
+    /// The machines which differ from the machines placed on the map
+    #[serde(default)]
+    pub(crate) machines: Vec<ScenarioMachine>,
+    /// Amount of hull breaches which are open at the start of the game
+    #[serde(default)]
+    pub(crate) breaches: u8,
+    /// The items the player starts with, missing items start with their amount of `START_ITEMS`
+    #[serde(default)]
+    pub(crate) inventory: BTreeMap<ItemId, i32>,
+    /// The resources of the player, missing resources start with their default amount
+    #[serde(default)]
+    pub(crate) resources: BTreeMap<ResourceKind, u16>,
+    /// The change rate of the player's resources, missing resources start with their default change rate
+    #[serde(default)]
+    pub(crate) resources_change: BTreeMap<ResourceKind, i16>,
+    /// The events of the scenario, if there are none the events of `assets/events.yaml` are used
+    #[serde(default)]
+    pub(crate) events: Option<EventTable>,
+    /// The objectives of the scenario, if there are none the objectives of `assets/objectives.yaml` are used
+    #[serde(default)]
+    pub(crate) objectives: Option<ObjectiveGraph>,
+    /// The steps of the tutorial which guides the player, random events only occur once all steps are done
+    #[serde(default)]
+    pub(crate) tutorial: Vec<TutorialStep>,
+}
+
+/// Returns the map all scenarios use unless they define their own
+pub(crate) fn default_map() -> String {
+    "station".to_string()
+}
+
+impl Scenario {
+    /// Returns the event table of this scenario
+    pub(crate) fn event_table(&self) -> &EventTable {
+        self.events.as_ref().unwrap_or_else(|| event_table())
+    }
+
+    /// Returns the objective graph of this scenario
+    pub(crate) fn objective_graph(&self) -> &ObjectiveGraph {
+        self.objectives
+            .as_ref()
+            .unwrap_or_else(|| objective_graph())
+    }
+}
+
+/// Returns all scenarios defined in `assets/scenarios`, the first one is the default scenario
+pub(crate) fn scenarios() -> &'static [Scenario] {
+    static SCENARIOS: OnceLock<Vec<Scenario>> = OnceLock::new();
+    SCENARIOS.get_or_init(|| {
+        [
+            include_str!("../../../assets/scenarios/crash_landing.yaml"),
+            include_str!("../../../assets/scenarios/dust_season.yaml"),
+            include_str!("../../../assets/scenarios/damaged_landing.yaml"),
+            include_str!("../../../assets/scenarios/outpost.yaml"),
+            include_str!("../../../assets/scenarios/tutorial.yaml"),
+        ]
+        .iter()
+        .map(|definition| {
+            serde_yaml::from_str(definition)
+                .expect("The scenario definitions in assets/scenarios are invalid")
+        })
+        .collect()
+    })
+}
+
+/// Returns the scenario with the given id, unknown ids fall back to the default scenario
+pub(crate) fn scenario(id: &str) -> &'static Scenario {
+    scenarios()
+        .iter()
+        .find(|scenario| scenario.id == id)
+        .unwrap_or(&scenarios()[0])
+}
+
+/// Returns the id of the default scenario, which is used by saves that do not name a scenario
+pub(crate) fn default_scenario_id() -> String {
+    scenarios()[0].id.clone()
+}
+
+impl GameState {
+    /// Returns the scenario of the current game
+    pub(crate) fn scenario(&self) -> &'static Scenario {
+        scenario(&self.scenario)
+    }
+
+    /// Returns the event table of the current game
+    pub(crate) fn event_table(&self) -> &'static EventTable {
+        self.scenario().event_table()
+    }
+
+    /// Returns the objective graph of the current game
+    pub(crate) fn objective_graph(&self) -> &'static ObjectiveGraph {
+        self.scenario().objective_graph()
+    }
+
+    /// Sets up the station, the player and the objectives as described by the scenario with the given id.
+    /// The machines and the spawn of the player are read from the map of the scenario.
+    /// # Arguments
+    /// * `id` - The id of the scenario
+    pub(crate) fn start_scenario(&mut self, id: &str) {
+        let scenario = scenario(id);
+        info!("Starting scenario {}", scenario.id);
+        self.scenario.clone_from(&scenario.id);
+        let map = map(&scenario.map);
+        self.machines = map
+            .machines
+            .iter()
+            .map(|machine| gen_machine(machine.id, machine.position, self.lng))
+            .collect();
+        for machine in &scenario.machines {
+            if let Some(position) = machine.position {
+                let mut result = gen_machine(machine.id, position, self.lng);
+                result.state = machine.state.clone();
+                self.machines.push(result);
+            } else {
+                self.machines
+                    .iter_mut()
+                    .filter(|result| result.id == machine.id)
+                    .for_each(|result| result.state = machine.state.clone());
+            }
+        }
+        if let Some(spawn) = map.spawn() {
+            self.player.position = spawn;
+        }
+        self.player.inventory = inventory_from(
+            START_ITEMS.into_iter().chain(scenario.inventory.clone()),
+            self.lng,
+        );
+        self.player.resources = START_RESOURCES.merged(scenario.resources.clone()).clamped();
+        self.player.resources_change =
+            START_RESOURCES_CHANGE.merged(scenario.resources_change.clone());
+        for _ in 0..scenario.breaches {
+            self.spawn_breach(1);
+        }
+        // Machines and breaches which start running already change the resources of the player
+        for machine in &self.machines {
+            if machine.state == State::Running {
+                self.player.resources_change =
+                    self.player.resources_change + machine.running_resources;
+            }
+        }
+        self.objectives = ObjectiveProgress::new(scenario.objective_graph());
+        self.tutorial = TutorialProgress::default();
+        self.rooms = RoomState::default();
+        self.eva = EvaState::default();
+        self.player.suit_oxygen = SUIT_OXYGEN_MAX;
+        self.update_collision();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::languages::Lang;
+
+    #[test]
+    fn test_scenarios() {
+        for scenario in scenarios() {
+            for id in scenario.objective_graph().start.iter().chain(
+                scenario
+                    .objective_graph()
+                    .objectives
+                    .iter()
+                    .flat_map(|o| &o.follow_ups),
+            ) {
+                assert!(scenario.objective_graph().get(id).is_some());
+            }
+            for definition in &scenario.event_table().events {
+                for follow_up in &definition.follow_ups {
+                    assert!(scenario.event_table().get(follow_up.event).is_some());
+                }
+            }
+        }
+        let mut gamestate = GameState::new_with_lang(Lang::En);
+        gamestate.start_scenario("damaged_landing");
+        assert_eq!(gamestate.scenario, "damaged_landing");
+        assert_eq!(gamestate.open_breaches(), 2);
+        // The breaches leak from the start
+        assert_ne!(gamestate.player.resources_change, START_RESOURCES_CHANGE);
+        assert_eq!(gamestate.player.resources[ResourceKind::Oxygen], 40000);
+        // Items missing from the scenario start with their default amount
+        gamestate.start_scenario("tutorial");
+        let amounts = gamestate
+            .player
+            .inventory
+            .iter()
+            .map(|(_, amount)| *amount)
+            .collect::<Vec<_>>();
+        assert_eq!(amounts, vec![2, 3, 1, 0]);
+        assert_eq!(scenario("unknown").id, default_scenario_id());
+    }
+}
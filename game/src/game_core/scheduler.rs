@@ -0,0 +1,384 @@
+//! This File contains the event definitions and the scheduler which decides when which event occurs
+use crate::backend::constants::{EventId, GameMode, ObjectId, DESIRED_FPS, FORECAST_POSITION};
+use crate::backend::gamestate::GameState;
+use crate::backend::screen::{Popup, ScreenCommand, StackCommand};
+use crate::backend::utils::get_draw_params;
+use crate::game_core::event::{Event, EventEffect};
+use crate::languages::{chain_averted, forecast};
+use crate::machines::machine::State;
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, Text, TextFragment};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tracing::info;
+
+/// A condition which has to be fulfilled for an event to occur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum Precondition {
+    /// The player has completed at least the given amount of objectives
+    #[serde(alias = "MinMilestone")]
+    CompletedObjectives(usize),
+    /// A machine with the given id is in the given state
+    MachineState(ObjectId, State),
+    /// The event with the given id is currently active
+    EventActive(EventId),
+}
+
+/// An event which follows another event after a delay, as long as its preconditions are still fulfilled.
+/// This is used to build chains of escalating events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FollowUp {
+    /// The event that follows
+    pub(crate) event: EventId,
+    /// Time in seconds between the start of the first event and the follow-up
+    pub(crate) delay: u32,
+    /// Conditions which all have to be fulfilled when the delay is over, otherwise the follow-up is averted
+    #[serde(default)]
+    pub(crate) preconditions: Vec<Precondition>,
+}
+
+/// Describes one event and the rules for when it can occur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct EventDefinition {
+    /// Stable identifier of the event
+    pub(crate) id: EventId,
+    /// The higher the weight, the more likely the event is chosen
+    pub(crate) weight: u32,
+    /// Conditions which all have to be fulfilled for the event to occur
+    #[serde(default)]
+    pub(crate) preconditions: Vec<Precondition>,
+    /// Minimal time in seconds between two occurrences of the event
+    #[serde(default)]
+    pub(crate) cooldown: u32,
+    /// Duration of the event in seconds
+    #[serde(default)]
+    pub(crate) duration: u32,
+    /// Effects the event has on the game
+    #[serde(default)]
+    pub(crate) effects: Vec<EventEffect>,
+    /// Events which are announced when this event starts and follow it later
+    #[serde(default)]
+    pub(crate) follow_ups: Vec<FollowUp>,
+}
+
+/// One stage of a difficulty curve, which defines how often events occur and how strong they are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CurveStage {
+    /// Time in seconds after the start of the game when the stage begins
+    pub(crate) after: u32,
+    /// Weight of the outcome that no event occurs
+    pub(crate) idle_weight: u32,
+    /// The strength of new events in percent
+    pub(crate) strength: u8,
+}
+
+/// Contains all event definitions and the settings of the scheduler
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct EventTable {
+    /// Time in seconds after the start of the game before the first event can occur
+    pub(crate) start_delay: u32,
+    /// Time in seconds between two rolls of the scheduler
+    pub(crate) interval: u32,
+    /// Weight of the outcome that no event occurs
+    pub(crate) idle_weight: u32,
+    /// Time in seconds the communication module predicts events with effects before they occur
+    pub(crate) forecast: u32,
+    /// The difficulty curve of the endless mode, sorted by the start of the stages
+    #[serde(default)]
+    pub(crate) endless_curve: Vec<CurveStage>,
+    /// Contains the definitions of all events
+    pub(crate) events: Vec<EventDefinition>,
+}
+
+impl EventTable {
+    /// Returns the definition of the event with the given id if there is one
+    pub(crate) fn get(&self, id: EventId) -> Option<&EventDefinition> {
+        self.events.iter().find(|definition| definition.id == id)
+    }
+
+    /// Returns the stage of the difficulty curve of the given game mode at the given time.
+    /// The story mode and tables without a curve never escalate.
+    /// # Arguments
+    /// * `mode` - The mode of the game
+    /// * `seconds` - The time in seconds since the start of the game
+    pub(crate) fn stage(&self, mode: GameMode, seconds: u32) -> CurveStage {
+        let base = CurveStage {
+            after: 0,
+            idle_weight: self.idle_weight,
+            strength: Event::FULL_STRENGTH,
+        };
+        match mode {
+            GameMode::Story => base,
+            GameMode::Endless => self
+                .endless_curve
+                .iter()
+                .take_while(|stage| stage.after <= seconds)
+                .last()
+                .copied()
+                .unwrap_or(base),
+        }
+    }
+}
+
+/// Returns the event table defined in `assets/events.yaml`
+pub(crate) fn event_table() -> &'static EventTable {
+    static EVENT_TABLE: OnceLock<EventTable> = OnceLock::new();
+    EVENT_TABLE.get_or_init(|| {
+        serde_yaml::from_str(include_str!("../../../assets/events.yaml"))
+            .expect("The event definitions in assets/events.yaml are invalid")
+    })
+}
+
+/// Remembers when each event occurred the last time to respect the cooldowns
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct EventScheduler {
+    /// Contains the time (in ticks) each event was triggered the last time
+    last_triggered: HashMap<EventId, u32>,
+    /// Contains the announced follow-ups together with the time (in ticks) they are due
+    #[serde(default)]
+    pending: Vec<(u32, FollowUp)>,
+}
+
+impl GameState {
+    /// Rolls for a new event once every interval of the event table, no events occur while the tutorial is running.
+    /// Only events whose preconditions are fulfilled and whose cooldown is over can be chosen.
+    /// If the communication module works, events with effects are forecast instead of starting right away.
+    /// # Returns
+    /// * `RLResult` - validates if the chosen event was triggered successfully
+    pub(crate) fn schedule_events(&mut self) -> RLResult {
+        self.resolve_follow_ups()?;
+        if self.is_tutorial_running() {
+            return Ok(());
+        }
+        let table = self.event_table();
+        let time = self.player.time;
+        if time < table.start_delay * DESIRED_FPS
+            || !time.is_multiple_of(table.interval * DESIRED_FPS)
+        {
+            return Ok(());
+        }
+        let candidates = table
+            .events
+            .iter()
+            .filter(|definition| self.is_event_possible(definition))
+            .collect::<Vec<_>>();
+        let stage = table.stage(self.mode, time / DESIRED_FPS);
+        let total_weight = stage.idle_weight + candidates.iter().map(|d| d.weight).sum::<u32>();
+        if total_weight == 0 {
+            return Ok(());
+        }
+        let mut roll = fastrand::u32(..total_weight);
+        for definition in candidates {
+            if roll < definition.weight {
+                if definition.effects.is_empty() || !self.has_forecast() {
+                    return self.trigger_event(definition.id);
+                }
+                info!("Forecasting event {:?}", definition.id);
+                self.scheduler.last_triggered.insert(definition.id, time);
+                self.scheduler.pending.push((
+                    time + table.forecast * DESIRED_FPS,
+                    FollowUp {
+                        event: definition.id,
+                        delay: table.forecast,
+                        preconditions: definition.preconditions.clone(),
+                    },
+                ));
+                return Ok(());
+            }
+            roll -= definition.weight;
+        }
+        Ok(())
+    }
+
+    /// Returns whether upcoming events are forecast, which is the case once the communication module is repaired
+    pub(crate) fn has_forecast(&self) -> bool {
+        self.machines.iter().any(|machine| {
+            machine.id == ObjectId::CommunicationModule && machine.state != State::Broken
+        })
+    }
+
+    /// Draws the forecast of the upcoming events with a countdown if the communication module works
+    /// # Arguments
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    pub(crate) fn draw_forecast(&self, canvas: &mut Canvas, scale: Vec2) {
+        if !self.has_forecast() || self.scheduler.pending.is_empty() {
+            return;
+        }
+        let mut upcoming = self.scheduler.pending.iter().collect::<Vec<_>>();
+        upcoming.sort_by_key(|(due, _)| *due);
+        let lines = upcoming
+            .iter()
+            .map(|(due, follow_up)| {
+                format!(
+                    "{} - {}s",
+                    follow_up.event.name(self.lng),
+                    due.saturating_sub(self.player.time) / DESIRED_FPS
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut text = Text::new(
+            TextFragment::new(format!("{}:\n{lines}", forecast(self.lng))).color(Color::WHITE),
+        );
+        text.set_scale(18.);
+        draw!(
+            canvas,
+            &text,
+            Vec2::new(FORECAST_POSITION.0, FORECAST_POSITION.1),
+            scale
+        );
+    }
+
+    /// Triggers all announced follow-ups which are due and whose preconditions are still fulfilled.
+    /// The player is informed about follow-ups that were averted.
+    /// # Returns
+    /// * `RLResult` - validates if the follow-ups were triggered successfully
+    fn resolve_follow_ups(&mut self) -> RLResult {
+        let time = self.player.time;
+        let (due, pending): (Vec<_>, Vec<_>) = self
+            .scheduler
+            .pending
+            .drain(..)
+            .partition(|(due, _)| *due <= time);
+        self.scheduler.pending = pending;
+        for (_, follow_up) in due {
+            if follow_up
+                .preconditions
+                .iter()
+                .all(|precondition| self.is_fulfilled(precondition))
+            {
+                self.trigger_event(follow_up.event)?;
+            } else {
+                info!("Follow-up {:?} was averted", follow_up.event);
+                let message = format!(
+                    "{} {}",
+                    chain_averted(self.lng),
+                    follow_up.event.name(self.lng)
+                );
+                self.get_screen_sender()?
+                    .send(StackCommand::Screen(ScreenCommand::Popup(Popup::info(
+                        message,
+                    ))))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the event can occur right now
+    /// # Arguments
+    /// * `definition` - The definition of the event
+    fn is_event_possible(&self, definition: &EventDefinition) -> bool {
+        let cooldown_over = self
+            .scheduler
+            .last_triggered
+            .get(&definition.id)
+            .is_none_or(|&last| {
+                self.player.time.saturating_sub(last) >= definition.cooldown * DESIRED_FPS
+            });
+        let already_active = self.events.iter().any(|event| event.id == definition.id);
+        let already_pending = self
+            .scheduler
+            .pending
+            .iter()
+            .any(|(_, follow_up)| follow_up.event == definition.id);
+        cooldown_over
+            && !already_active
+            && !already_pending
+            && definition
+                .preconditions
+                .iter()
+                .all(|precondition| self.is_fulfilled(precondition))
+    }
+
+    /// Returns whether the precondition is fulfilled in the current game
+    fn is_fulfilled(&self, precondition: &Precondition) -> bool {
+        match precondition {
+            Precondition::CompletedObjectives(amount) => self.objectives.completed.len() >= *amount,
+            Precondition::MachineState(id, state) => self
+                .machines
+                .iter()
+                .any(|machine| machine.id == *id && machine.state == *state),
+            Precondition::EventActive(id) => self.events.iter().any(|event| event.id == *id),
+        }
+    }
+
+    /// Starts the event with the given id and applies its effects.
+    /// The follow-ups of the event are announced to the player with a warning.
+    /// # Arguments
+    /// * `id` - The id of the event
+    /// # Returns
+    /// * `RLResult` - validates if the event was triggered successfully
+    pub(crate) fn trigger_event(&mut self, id: EventId) -> RLResult {
+        info!("Scheduling event {:?}", id);
+        let mut event = Event::from_id(id, self.event_table(), self.lng);
+        let strength = self
+            .event_table()
+            .stage(self.mode, self.player.time / DESIRED_FPS)
+            .strength;
+        event.action(strength, self)?;
+        self.scheduler.last_triggered.insert(id, self.player.time);
+        self.events.push(event);
+        let lng = self.lng;
+        for follow_up in self
+            .event_table()
+            .get(id)
+            .map_or(&[][..], |d| &d.follow_ups)
+        {
+            self.get_screen_sender()?
+                .send(StackCommand::Screen(ScreenCommand::Popup(Popup::warning(
+                    follow_up.event.chain_warning(lng).to_string(),
+                ))))?;
+            let due = self.player.time + follow_up.delay * DESIRED_FPS;
+            self.scheduler.pending.push((due, follow_up.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_event_table() {
+        let table = event_table();
+        for id in [
+            EventId::Sandstorm,
+            EventId::CometStrike,
+            EventId::PowerFailure,
+            EventId::InformationNasa,
+            EventId::InformationMars,
+            EventId::SeismicTremor,
+            EventId::Fire,
+            EventId::StormDamage,
+        ] {
+            assert!(table.get(id).is_some(), "{id:?} is not defined");
+        }
+        // every follow-up has to be defined as well
+        for definition in &table.events {
+            for follow_up in &definition.follow_ups {
+                assert!(table.get(follow_up.event).is_some());
+            }
+        }
+        assert!(table.interval > 0);
+        assert!(table
+            .endless_curve
+            .windows(2)
+            .all(|stages| stages[0].after < stages[1].after));
+    }
+
+    #[test]
+    fn test_difficulty_curve() {
+        let table = event_table();
+        let story = table.stage(GameMode::Story, 100_000);
+        assert_eq!(story.idle_weight, table.idle_weight);
+        assert_eq!(story.strength, 100);
+        let start = table.stage(GameMode::Endless, 0);
+        let late = table.stage(GameMode::Endless, 100_000);
+        assert!(late.idle_weight < start.idle_weight);
+        assert!(late.strength > start.strength);
+    }
+}
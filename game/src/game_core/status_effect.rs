@@ -0,0 +1,92 @@
+//! This File contains the status effects, conditions of the player which affect life, movement and trades
+use crate::backend::constants::DESIRED_FPS;
+use crate::languages::{status_names, Lang};
+use serde::{Deserialize, Serialize};
+
+/// The kind of a status effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusKind {
+    /// The station has no energy left, so the heating is off
+    Hypothermia = 0,
+    /// The station has no oxygen left
+    Hypoxia = 1,
+    /// The player is exhausted, which makes them slower
+    Exhaustion = 2,
+    /// The player got hurt, injuries stack up to `StatusKind::max_stacks`
+    Injury = 3,
+    /// The player has nothing left to eat
+    Starvation = 4,
+    /// The player has nothing left to drink
+    Dehydration = 5,
+}
+
+impl StatusKind {
+    pub fn t(self, lng: Lang) -> &'static str {
+        status_names(lng)[self as usize]
+    }
+
+    /// Returns the amount of life the player loses every tick per stack of this effect
+    pub(crate) fn life_drain(self) -> i16 {
+        match self {
+            StatusKind::Hypothermia => 10,
+            StatusKind::Hypoxia => 50,
+            StatusKind::Exhaustion => 0,
+            StatusKind::Injury => 2,
+            StatusKind::Starvation => 8,
+            StatusKind::Dehydration => 15,
+        }
+    }
+
+    /// Returns the movement speed of the player in percent while this effect is active
+    pub(crate) fn speed_percent(self) -> usize {
+        match self {
+            StatusKind::Hypothermia => 70,
+            StatusKind::Hypoxia => 100,
+            StatusKind::Exhaustion => 60,
+            StatusKind::Injury | StatusKind::Starvation => 80,
+            StatusKind::Dehydration => 90,
+        }
+    }
+
+    /// Returns the duration of trades in percent while this effect is active
+    pub(crate) fn trade_time_percent(self) -> u32 {
+        match self {
+            StatusKind::Hypothermia => 125,
+            StatusKind::Hypoxia | StatusKind::Injury => 100,
+            StatusKind::Starvation | StatusKind::Dehydration => 120,
+            StatusKind::Exhaustion => 150,
+        }
+    }
+
+    /// Returns how often this effect can stack, applying an effect which can't stack any more refreshes its duration
+    pub(crate) fn max_stacks(self) -> u8 {
+        match self {
+            StatusKind::Injury => 3,
+            _ => 1,
+        }
+    }
+}
+
+/// A status effect the player currently suffers from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub(crate) kind: StatusKind,
+    /// Contains the amount of ticks until the effect wears off
+    pub(crate) remaining: u32,
+    /// Contains how often the effect is stacked
+    pub(crate) stacks: u8,
+}
+
+impl StatusEffect {
+    /// Creates a new `StatusEffect` with one stack
+    /// # Arguments
+    /// * `kind` - The kind of the effect
+    /// * `duration` - The duration of the effect in seconds
+    pub(crate) fn new(kind: StatusKind, duration: u32) -> Self {
+        Self {
+            kind,
+            remaining: duration * DESIRED_FPS,
+            stacks: 1,
+        }
+    }
+}
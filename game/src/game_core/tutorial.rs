@@ -0,0 +1,178 @@
+//! This File contains the tutorial, which guides the player step by step through the first minutes of a scenario
+use crate::backend::constants::{ObjectId, DESIRED_FPS, TUTORIAL_POSITION};
+use crate::backend::gamestate::GameState;
+use crate::backend::rlcolor::RLColor;
+use crate::backend::utils::get_draw_params;
+use crate::game_core::objective::LocalizedText;
+use crate::languages::tutorial_title;
+use crate::machines::machine::State;
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, DrawMode, Mesh, Rect, Text, TextFragment};
+use ggez::Context;
+use serde::{Deserialize, Serialize};
+
+/// Width of the box which shows the current step of the tutorial
+const GUIDE_WIDTH: f32 = 900.;
+
+/// What the player has to do to finish a step of the tutorial
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum StepGoal {
+    /// The player stands in the interaction area of a machine with the given id
+    Reach(ObjectId),
+    /// The player opened the trades of a machine with the given id
+    Interact(ObjectId),
+    /// A machine with the given id is repaired
+    Repair(ObjectId),
+    /// A machine with the given id is running
+    Run(ObjectId),
+    /// The given amount of seconds passed since the step started
+    Wait(u32),
+}
+
+/// One step of the tutorial
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TutorialStep {
+    /// The instructions shown to the player
+    pub(crate) text: LocalizedText,
+    /// The machine whose interaction area is highlighted during the step
+    #[serde(default)]
+    pub(crate) highlight: Option<ObjectId>,
+    pub(crate) goal: StepGoal,
+}
+
+/// The progress of the player in the tutorial of the scenario
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TutorialProgress {
+    /// Index of the current step
+    pub(crate) step: usize,
+    /// The in-game time at which the current step started
+    pub(crate) since: u32,
+}
+
+impl GameState {
+    /// Returns the current step of the tutorial, or None if the scenario has no tutorial or it is finished
+    pub(crate) fn tutorial_step(&self) -> Option<&'static TutorialStep> {
+        self.scenario().tutorial.get(self.tutorial.step)
+    }
+
+    /// Returns whether the tutorial of the scenario is still running, random events are suppressed until it is finished
+    pub(crate) fn is_tutorial_running(&self) -> bool {
+        self.tutorial_step().is_some()
+    }
+
+    /// Advances the tutorial to the next step once the goal of the current step is reached
+    pub(crate) fn update_tutorial(&mut self) {
+        let Some(step) = self.tutorial_step() else {
+            return;
+        };
+        if self.is_goal_reached(&step.goal) {
+            self.tutorial = TutorialProgress {
+                step: self.tutorial.step + 1,
+                since: self.player.time,
+            };
+        }
+    }
+
+    /// Returns whether the goal of a tutorial step is reached
+    /// # Arguments
+    /// * `goal` - The goal of the step
+    fn is_goal_reached(&self, goal: &StepGoal) -> bool {
+        let any_machine = |id: ObjectId, condition: &dyn Fn(&State) -> bool| {
+            self.machines
+                .iter()
+                .any(|machine| machine.id == id && condition(&machine.state))
+        };
+        match goal {
+            StepGoal::Reach(id) => self
+                .machines
+                .iter()
+                .any(|machine| machine.id == *id && machine.is_interactable(self.player.position)),
+            StepGoal::Interact(id) => self
+                .trade_panel
+                .as_ref()
+                .and_then(|panel| self.machines.get(panel.machine))
+                .is_some_and(|machine| machine.id == *id),
+            StepGoal::Repair(id) => any_machine(*id, &|state| *state != State::Broken),
+            StepGoal::Run(id) => any_machine(*id, &|state| *state == State::Running),
+            StepGoal::Wait(seconds) => {
+                self.player.time.saturating_sub(self.tutorial.since) >= seconds * DESIRED_FPS
+            }
+        }
+    }
+
+    /// Draws the instructions of the current tutorial step and highlights the interaction area of its machine
+    /// # Arguments
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// * `RLResult` - validates if the meshes were created successfully
+    pub(crate) fn draw_tutorial(
+        &self,
+        canvas: &mut Canvas,
+        scale: Vec2,
+        ctx: &Context,
+    ) -> RLResult {
+        let Some(step) = self.tutorial_step() else {
+            return Ok(());
+        };
+        if let Some(id) = step.highlight {
+            for machine in self.machines.iter().filter(|machine| machine.id == id) {
+                let area = machine.interaction_area;
+                let highlight = Mesh::new_rounded_rectangle(
+                    ctx,
+                    DrawMode::stroke(4.),
+                    Rect::new(0., 0., area.w, area.h),
+                    8.,
+                    RLColor::GOLD,
+                )?;
+                draw!(canvas, &highlight, Vec2::new(area.x, area.y), scale);
+            }
+        }
+        let mut text = Text::new(
+            TextFragment::new(format!(
+                "{} ({}/{})\n",
+                tutorial_title(self.lng),
+                self.tutorial.step + 1,
+                self.scenario().tutorial.len()
+            ))
+            .color(RLColor::GOLD),
+        );
+        text.add(TextFragment::new(step.text.get(self.lng)).color(RLColor::BLACK));
+        text.set_scale(22.);
+        text.set_bounds(Vec2::new(GUIDE_WIDTH - 30., f32::INFINITY));
+        let background = Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(-15., -10., GUIDE_WIDTH, 100.),
+            10.,
+            RLColor::LIGHT_GREY,
+        )?;
+        let position = Vec2::new(TUTORIAL_POSITION.0, TUTORIAL_POSITION.1);
+        draw!(canvas, &background, position, scale);
+        draw!(canvas, &text, position, scale);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::languages::Lang;
+
+    #[test]
+    fn test_tutorial() {
+        let mut gamestate = GameState::new_with_lang(Lang::En);
+        gamestate.start_scenario("tutorial");
+        assert!(gamestate.is_tutorial_running());
+        gamestate.update_tutorial();
+        assert_eq!(gamestate.tutorial.step, 0);
+        // The first step is done once the player reaches the oxygen generator in the top left
+        gamestate.player.position = (400., 420.);
+        gamestate.update_tutorial();
+        assert_eq!(gamestate.tutorial.step, 1);
+        gamestate.tutorial.step = gamestate.scenario().tutorial.len();
+        assert!(!gamestate.is_tutorial_running());
+    }
+}
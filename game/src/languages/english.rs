@@ -6,6 +6,12 @@ pub const GEDRUCKTESTEIL: [&str; 3] = [
     "A 3D-printed part that can be used to repair the communication module",
     "3D-gedrucktes-Teil.png",
 ];
+/// Constant for the item `Schrott`
+pub const SCRAP: [&str; 3] = [
+    "Scrap",
+    "Scrap found outside of the station, the 3D printer can recycle it into printed parts",
+    "Schrott.png",
+];
 /// Constant for the item `Superglue`
 pub const SUPER_GLUE: [&str; 3] = [
     "SuperGlue",
@@ -20,15 +26,31 @@ pub const PETROL: [&str; 3] = [
 ];
 
 /// Constant for the resource names.
-pub(crate) const RESOURCE_NAME: [&str; 3] = ["Air", "Energy", "Life"];
+pub(crate) const RESOURCE_NAME: [&str; 6] = ["Air", "Energy", "Life", "Food", "Water", "Fatigue"];
 
 /// The text for the warning-`Popup`s that appears in the top left corner.
-pub const WARNINGS: [&str; 4] = [
+pub const WARNINGS: [&str; 7] = [
     "A comet is on its way!",
     "The power's out!",
     "A sandstorm is on its way!",
     "A machine is down!",
+    "The ground is shaking!",
+    "A fire broke out at the power generator!",
+    "The sandstorm damaged the oxygen generator!",
+];
+/// The warnings announcing an event that follows another one, indexed by `EventId`.
+pub const CHAIN_WARNINGS: [&str; 8] = [
+    "A sandstorm is forming.",
+    "The tremors are getting stronger, a comet is about to hit the station!",
+    "The power grid is unstable.",
+    "NASA is about to send a message.",
+    "A message about Mars is coming in.",
+    "Seismic activity detected.",
+    "The power generator is overheating, restart it before it catches fire!",
+    "The sandstorm is getting stronger, the oxygen generator might get damaged!",
 ];
+/// The text of the `Popup` that appears if an announced event does not happen.
+pub const CHAIN_AVERTED: &str = "Danger averted:";
 /// The text for the mars-info-`Popup`s that appears in the top left corner.
 pub const MARS_INFO: [&str; 5] = [
     "Mars is the 4th planet in our solar system",
@@ -52,6 +74,9 @@ pub const GAME_INFO: [&str; 1] = ["Life regeneration started"];
 pub const AIR_STRING: &str = "too little air";
 pub const ENERGY_STRING: &str = "Cold";
 pub const AIR_AND_ENERGY_STRING: &str = "Cold and too little air";
+pub const INJURY_STRING: &str = "Injuries";
+pub const HUNGER_STRING: &str = "Hunger";
+pub const THIRST_STRING: &str = "Thirst";
 pub const DEATH_REASON_STRING: &str = "You died of";
 pub const ADDITIONAL_INFO_STRING: &str = "Please press ESC!";
 pub const RESUME_ERROR_STRING: &str = "You need a score first";
@@ -63,7 +88,7 @@ Hopefully, you'll be able to repair communications,
 so that you can be rescued.";
 
 pub const TUTORIAL_TEXT: &str =
-    "Move around with WASD. Interact with E.\nFor reference, you have your manual on H.";
+    "Move around with WASD. Interact with E.\nFor reference, you have your manual on H and your inventory on I.";
 
 /// Constant for the Text used in the `Button` info
 pub const BUTTON_INFO: &str = "Please press the space bar!";
@@ -92,49 +117,154 @@ pub const INFORMATIONS_POPUP_MARS: [&str; 2] = [
     "Informations popup Mars",
     "An information popup about Mars containing facts and information about Mars",
 ];
+pub const SEISMIC_TREMOR: [&str; 2] = [
+    "Seismic tremor",
+    "A seismic tremor, which announces a comet strike",
+];
+pub const FIRE: [&str; 2] = [
+    "Fire",
+    "A fire at the overheated power generator, which burns oxygen and destroys the generator",
+];
+pub const STORM_DAMAGE: [&str; 2] = [
+    "Storm damage",
+    "A long sandstorm, which damages the oxygen generator",
+];
 /// Constants for the trade conflict.
-pub const TRADE_CONFLICT_POPUP: [&str; 1] =
-    ["The following items are missing to execute the trade:"];
+pub const TRADE_CONFLICT_POPUP: [&str; 2] = [
+    "The following items are missing to execute the trade:",
+    "There is not enough space in the inventory for the following items:",
+];
+
+/// Contains the names of all trades, indexed by `TradeId`.
+pub(crate) const TRADE_NAMES: [&str; 36] = [
+    "Repair",
+    "Start",
+    "Stop",
+    "Refuel",
+    "Start",
+    "Stop",
+    "Repair",
+    "Produce SuperGlue",
+    "Repair",
+    "Print part",
+    "Repair",
+    "Send emergency signal",
+    "Seal hole",
+    "Upgrade",
+    "Upgrade",
+    "Start",
+    "Stop",
+    "Seal airlock",
+    "Reroute energy",
+    "Repair",
+    "Start",
+    "Stop",
+    "Repair",
+    "Start",
+    "Stop",
+    "Repair",
+    "Sleep",
+    "Store SuperGlue",
+    "Take SuperGlue",
+    "Store petrol",
+    "Take petrol",
+    "Store printed part",
+    "Take printed part",
+    "Store scrap",
+    "Take scrap",
+    "Recycle scrap",
+];
+/// Constants for the trade panel.
+pub(crate) const TRADE_PANEL: [&str; 8] = [
+    "Cost",
+    "Yield",
+    "Time",
+    "Missing",
+    "Not available right now",
+    "W/S: select, E: confirm, Q: close",
+    "Tier",
+    "Stored",
+];
 
+/// Constants for the build mode.
+pub(crate) const BUILD_MODE: [&str; 3] = [
+    "Build mode",
+    "Tab: change blueprint, E: build, B: close",
+    "Not enough space",
+];
+/// Contains the names of the difficulties.
+pub(crate) const DIFFICULTY_NAMES: [&str; 3] = ["Easy", "Normal", "Hard"];
+/// Contains the names of the game modes.
+pub(crate) const GAME_MODE_NAMES: [&str; 2] = ["Story", "Endless"];
+/// Contains the names of the status effects, indexed by `StatusKind`.
+pub(crate) const STATUS_NAMES: [&str; 6] = [
+    "Hypothermia",
+    "Hypoxia",
+    "Exhaustion",
+    "Injury",
+    "Starvation",
+    "Dehydration",
+];
+/// Contains the label of the weight the player carries.
+pub(crate) const CARRY_WEIGHT: &str = "Weight";
+/// Constants for the inventory screen.
+pub(crate) const INVENTORY: [&str; 5] = [
+    "Inventory",
+    "Used by",
+    "Produced by",
+    "nothing",
+    "I/Esc: close",
+];
+/// Constants for the scenario picker.
+pub(crate) const SCENARIO_PICKER: [&str; 3] = [
+    "Choose a scenario",
+    "W/S: select, M: change mode, Enter: start, Esc: back",
+    "Mode",
+];
+/// Contains the texts which show the time the player survived in the endless mode.
+pub(crate) const SURVIVAL_TIME: [&str; 2] = ["Survived", "You survived"];
+/// Constants for the objective tracker.
+pub(crate) const OBJECTIVE_TRACKER: [&str; 6] = [
+    "Objectives",
+    "O: show",
+    "O: hide",
+    "repaired",
+    "broken",
+    "Survive",
+];
+/// Contains the title of the forecast of upcoming events.
+pub(crate) const FORECAST: &str = "Forecast";
+/// Contains the label of the air pressure of a room.
+pub(crate) const ROOM_PRESSURE: &str = "pressure";
+/// Constant for the oxygen in the suit of the player.
+pub(crate) const SUIT_OXYGEN: &str = "Suit oxygen";
+/// Constants for scavenging outside of the station.
+pub(crate) const SCAVENGE: [&str; 2] = [
+    "You found:",
+    "There is nothing left to find here, come back later",
+];
+/// Contains the title of the tutorial guide.
+pub(crate) const TUTORIAL_TITLE: &str = "Tutorial";
 /// Constants for the `time_name`.
 pub const TIME_NAME: [&str; 1] = ["Time"];
 
 /// Constants for the text of the button in the main menu
-pub const BUTTON_TEXT: [&str; 4] = ["Continue", "New Game", "Exit", "German"];
+pub const BUTTON_TEXT: [&str; 5] = ["Continue", "New Game", "Exit", "German", "Difficulty"];
 
 /// Contains all machine names as a vec of strings.
-pub(crate) const MACHINE_NAMES: [&str; 7] = [
+pub(crate) const MACHINE_NAMES: [&str; 11] = [
     "Oxygen generator",
     "power generator",
     "work machine",
     "3D printer",
     "communication module",
     "Hole",
-    "Hole",
-];
-
-/// Contains the Messages that are displayed in the Handbook
-pub(crate) const FIRST_MILESTONE_HANDBOOK_TEXT: [&str; 10] = [
-    "- Repair the oxygen generator (top left)",
-    "- Repair the electricity generator (bottom left)",
-    "- Comets create holes in the walls",
-    "- Repair holes with SuperGlue",
-    "- In case of a power failure",
-    "you must restart the power generator",
-    "- Remember to use petrol sparingly",
-    "- You can stop the generator briefly,",
-    " if you have enough power",
-    "\n\n Press H to close",
+    "solar panel",
+    "storage locker",
+    "hydroponics",
+    "water recycler",
+    "bunk",
 ];
 
-pub(crate) const SECOND_MILESTONE_HANDBOOK_TEXT: [&str; 7] = [
-    "- Repair the communication system (right)",
-    "- Send a message to be rescued",
-    "- Your power may still fail,",
-    "while you're sending the message!",
-    "- When you send the message,",
-    "you automatically win.",
-    "\n\n Press H to close",
-];
-pub(crate) const SEND_MSG_FAILURE: &str =
-    "The message cannot be sent until the system is restored.";
+/// Contains the hint how to close the handbook
+pub(crate) const HANDBOOK_CLOSE: &str = "Press H to close";
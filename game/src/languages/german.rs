@@ -6,6 +6,12 @@ pub const GEDRUCKTESTEIL: [&str; 3] = [
     "Ein 3D-gedrucktes-Teil, welches zur Reparatur des Kommunikationsmoduls verwendet werden kann",
     "3D-gedrucktes-Teil.png",
 ];
+/// Constant for the item `Schrott`
+pub const SCRAP: [&str; 3] = [
+    "Schrott",
+    "Schrott von außerhalb der Station, der 3D-Drucker kann ihn zu gedruckten Teilen verarbeiten",
+    "Schrott.png",
+];
 /// Constant for the item `Superglue`
 pub const SUPER_GLUE: [&str; 3] = [
     "SuperGlue",
@@ -20,15 +26,32 @@ pub const PETROL: [&str; 3] = [
 ];
 
 /// Constant for the resource names.
-pub(crate) const RESOURCE_NAME: [&str; 3] = ["Luft", "Energie", "Leben"];
+pub(crate) const RESOURCE_NAME: [&str; 6] =
+    ["Luft", "Energie", "Leben", "Nahrung", "Wasser", "Müdigkeit"];
 
 /// The text for the warning-`Popup`s that appears in the top left corner.
-pub const WARNINGS: [&str; 4] = [
+pub const WARNINGS: [&str; 7] = [
     "Ein Komet ist auf dem Weg!",
     "Der Strom ist ausgefallen!",
     "Ein Sandsturm ist auf dem Weg!",
     "Eine Maschine ist ausgefallen!",
+    "Der Boden bebt!",
+    "Am Stromgenerator ist ein Feuer ausgebrochen!",
+    "Der Sandsturm hat den Sauerstoffgenerator beschädigt!",
+];
+/// The warnings announcing an event that follows another one, indexed by `EventId`.
+pub const CHAIN_WARNINGS: [&str; 8] = [
+    "Ein Sandsturm zieht auf.",
+    "Die Erschütterungen werden stärker, gleich schlägt ein Komet in die Station ein!",
+    "Das Stromnetz ist instabil.",
+    "Die NASA wird gleich eine Nachricht senden.",
+    "Eine Nachricht über den Mars geht ein.",
+    "Seismische Aktivität entdeckt.",
+    "Der Stromgenerator überhitzt, starte ihn neu, bevor er Feuer fängt!",
+    "Der Sandsturm wird stärker, der Sauerstoffgenerator könnte beschädigt werden!",
 ];
+/// The text of the `Popup` that appears if an announced event does not happen.
+pub const CHAIN_AVERTED: &str = "Gefahr abgewendet:";
 /// The text for the mars-info-`Popup`s that appears in the top left corner.
 pub const MARS_INFO: [&str; 5] = [
     "Der Mars ist der 4. Planet in unserem Sonnensystem",
@@ -51,6 +74,9 @@ pub const GAME_INFO: [&str; 1] = ["Lebensregeneration gestartet"];
 pub const AIR_STRING: &str = "zu wenig Luft";
 pub const ENERGY_STRING: &str = "Kälte";
 pub const AIR_AND_ENERGY_STRING: &str = "Kälte und zu wenig Luft";
+pub const INJURY_STRING: &str = "Verletzungen";
+pub const HUNGER_STRING: &str = "Hunger";
+pub const THIRST_STRING: &str = "Durst";
 pub const DEATH_REASON_STRING: &str = "Du bist gestorben an";
 pub const ADDITIONAL_INFO_STRING: &str = "Bitte drücke ESC!";
 pub const RESUME_ERROR_STRING: &str = "Du brauchst zuerst einen Spielstand";
@@ -59,7 +85,7 @@ pub const RESUME_ERROR_STRING: &str = "Du brauchst zuerst einen Spielstand";
 pub const INTRO_TEXT: &str = "Du bist auf dem Mars gestrandet und musst überleben.\nDazu musst du die \
 Sauerstoffproduktion wiederherstellen.\nHoffentlich schaffst du es, die Kommunikation zu reparieren, \ndamit du gerettet werden kannst.\n";
 pub const TUTORIAL_TEXT: &str =
-    "Bewege dich mit WASD. Interagiere mit E.\nZum Nachschlagen hast du auf H dein Handbuch.";
+    "Bewege dich mit WASD. Interagiere mit E.\nZum Nachschlagen hast du auf H dein Handbuch und auf I dein Inventar.";
 
 /// Constant for the Text used in the `Button` info
 pub const BUTTON_INFO: &str = "Bitte drücke die Leertaste!";
@@ -88,44 +114,155 @@ pub const INFORMATIONS_POPUP_MARS: [&str; 2] = [
     "InformationspopupMars",
     "Ein Informationspopup über Mars, welches Fakten und Informationen über den Mars enthält",
 ];
+pub const SEISMIC_TREMOR: [&str; 2] = [
+    "Erdbeben",
+    "Ein Erdbeben, welches einen Kometeneinschlag ankündigt",
+];
+pub const FIRE: [&str; 2] = [
+    "Feuer",
+    "Ein Feuer am überhitzten Stromgenerator, welches Sauerstoff verbrennt und den Generator zerstört",
+];
+pub const STORM_DAMAGE: [&str; 2] = [
+    "Sturmschaden",
+    "Ein langer Sandsturm, welcher den Sauerstoffgenerator beschädigt",
+];
 /// Constants for the trade conflict.
-pub const TRADE_CONFLICT_POPUP: [&str; 1] = ["Es fehlen folgende Items, um den Trade auszuführen:"];
+pub const TRADE_CONFLICT_POPUP: [&str; 2] = [
+    "Es fehlen folgende Items, um den Trade auszuführen:",
+    "Im Inventar ist nicht genug Platz für folgende Items:",
+];
+/// Contains the names of all trades, indexed by `TradeId`.
+pub(crate) const TRADE_NAMES: [&str; 36] = [
+    "Reparieren",
+    "Starten",
+    "Stoppen",
+    "Betanken",
+    "Starten",
+    "Stoppen",
+    "Reparieren",
+    "SuperGlue herstellen",
+    "Reparieren",
+    "Teil drucken",
+    "Reparieren",
+    "Notsignal senden",
+    "Loch abdichten",
+    "Aufrüsten",
+    "Aufrüsten",
+    "Starten",
+    "Stoppen",
+    "Luftschleuse abdichten",
+    "Energie umleiten",
+    "Reparieren",
+    "Starten",
+    "Stoppen",
+    "Reparieren",
+    "Starten",
+    "Stoppen",
+    "Reparieren",
+    "Schlafen",
+    "SuperGlue einlagern",
+    "SuperGlue entnehmen",
+    "Benzin einlagern",
+    "Benzin entnehmen",
+    "Gedrucktes Teil einlagern",
+    "Gedrucktes Teil entnehmen",
+    "Schrott einlagern",
+    "Schrott entnehmen",
+    "Schrott verwerten",
+];
+/// Constants for the trade panel.
+pub(crate) const TRADE_PANEL: [&str; 8] = [
+    "Kosten",
+    "Ertrag",
+    "Dauer",
+    "Fehlt",
+    "Gerade nicht verfügbar",
+    "W/S: auswählen, E: bestätigen, Q: schließen",
+    "Stufe",
+    "Eingelagert",
+];
+/// Constants for the build mode.
+pub(crate) const BUILD_MODE: [&str; 3] = [
+    "Baumodus",
+    "Tab: Bauplan wechseln, E: bauen, B: schließen",
+    "Nicht genug Platz",
+];
+/// Contains the names of the difficulties.
+pub(crate) const DIFFICULTY_NAMES: [&str; 3] = ["Leicht", "Normal", "Schwer"];
+/// Contains the names of the game modes.
+pub(crate) const GAME_MODE_NAMES: [&str; 2] = ["Geschichte", "Endlos"];
+/// Contains the names of the status effects, indexed by `StatusKind`.
+pub(crate) const STATUS_NAMES: [&str; 6] = [
+    "Unterkühlung",
+    "Sauerstoffmangel",
+    "Erschöpfung",
+    "Verletzung",
+    "Hunger",
+    "Austrocknung",
+];
+/// Contains the label of the weight the player carries.
+pub(crate) const CARRY_WEIGHT: &str = "Gewicht";
+/// Constants for the inventory screen.
+pub(crate) const INVENTORY: [&str; 5] = [
+    "Inventar",
+    "Benötigt von",
+    "Hergestellt von",
+    "nichts",
+    "I/Esc: schließen",
+];
+/// Constants for the scenario picker.
+pub(crate) const SCENARIO_PICKER: [&str; 3] = [
+    "Wähle ein Szenario",
+    "W/S: auswählen, M: Modus wechseln, Enter: starten, Esc: zurück",
+    "Modus",
+];
+/// Contains the texts which show the time the player survived in the endless mode.
+pub(crate) const SURVIVAL_TIME: [&str; 2] = ["Überlebt", "Du hast überlebt:"];
+/// Constants for the objective tracker.
+pub(crate) const OBJECTIVE_TRACKER: [&str; 6] = [
+    "Ziele",
+    "O: anzeigen",
+    "O: verbergen",
+    "repariert",
+    "kaputt",
+    "Überlebe",
+];
+/// Contains the title of the forecast of upcoming events.
+pub(crate) const FORECAST: &str = "Vorhersage";
+/// Contains the label of the air pressure of a room.
+pub(crate) const ROOM_PRESSURE: &str = "Luftdruck";
+/// Constant for the oxygen in the suit of the player.
+pub(crate) const SUIT_OXYGEN: &str = "Anzugsauerstoff";
+/// Constants for scavenging outside of the station.
+pub(crate) const SCAVENGE: [&str; 2] = [
+    "Du hast gefunden:",
+    "Hier gibt es nichts mehr zu finden, komm später wieder",
+];
+/// Contains the title of the tutorial guide.
+pub(crate) const TUTORIAL_TITLE: &str = "Einführung";
 /// Constants for the `time_name`.
 pub const TIME_NAME: [&str; 1] = ["Zeit"];
 /// Constants for the text of the button in the main menu
-pub const BUTTON_TEXT: [&str; 4] = ["Fortsetzen", "Neues Spiel", "Beenden", "English"];
+pub const BUTTON_TEXT: [&str; 5] = [
+    "Fortsetzen",
+    "Neues Spiel",
+    "Beenden",
+    "English",
+    "Schwierigkeit",
+];
 /// Contains all machine names as a vec of strings.
-pub(crate) const MACHINE_NAMES: [&str; 7] = [
+pub(crate) const MACHINE_NAMES: [&str; 11] = [
     "Sauerstoffgenerator",
     "Stromgenerator",
     "Werkermaschine",
     "3D-Drucker",
     "Kommunikationsmodul",
     "Loch",
-    "Loch",
+    "Solarpanel",
+    "Lagerkiste",
+    "Hydrokultur",
+    "Wasseraufbereitung",
+    "Koje",
 ];
-/// Contains the Messages that are displayed in the Handbook
-pub(crate) const FIRST_MILESTONE_HANDBOOK_TEXT: [&str; 10] = [
-    "- Repariere den Sauerstoffgenerator (oben links)",
-    "- Repariere den Stromgenerator (unten links)",
-    "- Kometen erzeugen Löcher in den Wänden",
-    "- Löcher werden mit SuperGlue repariert",
-    "- Bei einem Stromausfall",
-    "   musst du den Stromgenerator erneut starten.",
-    "- Denk daran sparsam mit Benzin umzugehen!",
-    "- Du kannst den Generator kurz anhalten,",
-    "   wenn du genug Energie hast",
-    "\n\n           Drücke H zum schließen",
-];
-
-pub(crate) const SECOND_MILESTONE_HANDBOOK_TEXT: [&str; 7] = [
-    "- Repariere das Kommunikationssystem (rechts)",
-    "- Sende eine Nachricht ab um gerettet zu werden",
-    "- Dein Strom kann immernoch ausfallen,",
-    "   während du die Nachricht sendest!",
-    "- Wenn du die Nachricht abgeschickt hast,",
-    "   gewinnst du automatisch.",
-    "\n\n             Drücke H zum schließen",
-];
-pub(crate) const SEND_MSG_FAILURE: &str =
-    "Die Nachricht kann nicht gesendet werden solange das System nicht wiederhergestellt ist";
+/// Contains the hint how to close the handbook
+pub(crate) const HANDBOOK_CLOSE: &str = "Drücke H zum schließen";
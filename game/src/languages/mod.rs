@@ -37,39 +37,54 @@ macro_rules! t {
 t!(petrol, 3, PETROL);
 t!(printed_part, 3, GEDRUCKTESTEIL);
 t!(super_glue, 3, SUPER_GLUE);
-t!(machine_names, 7, MACHINE_NAMES);
+t!(scrap, 3, SCRAP);
+t!(machine_names, 11, MACHINE_NAMES);
+t!(difficulty_names, 3, DIFFICULTY_NAMES);
+t!(game_mode_names, 2, GAME_MODE_NAMES);
+t!(status_names, 6, STATUS_NAMES);
 t!(game_info, 1, GAME_INFO);
 t!(mars_info, 5, MARS_INFO);
 t!(nasa_info, 5, NASA_INFO);
-t!(warnings, 4, WARNINGS);
-t!(button_text, 4, BUTTON_TEXT);
-t!(trade_conflict_popup, 1, TRADE_CONFLICT_POPUP);
-t!(
-    first_milestone_handbook_text,
-    10,
-    FIRST_MILESTONE_HANDBOOK_TEXT
-);
-t!(
-    second_milestone_handbook_text,
-    7,
-    SECOND_MILESTONE_HANDBOOK_TEXT
-);
+t!(warnings, 7, WARNINGS);
+t!(chain_warnings, 8, CHAIN_WARNINGS);
+t!(button_text, 5, BUTTON_TEXT);
+t!(trade_conflict_popup, 2, TRADE_CONFLICT_POPUP);
+t!(trade_names, 36, TRADE_NAMES);
+t!(build_mode, 3, BUILD_MODE);
+t!(trade_panel, 8, TRADE_PANEL);
+t!(inventory, 5, INVENTORY);
+t!(objective_tracker, 6, OBJECTIVE_TRACKER);
+t!(scenario_picker, 3, SCENARIO_PICKER);
+t!(survival_time, 2, SURVIVAL_TIME);
 t!(time_name, 1, TIME_NAME);
 t!(informations_popup_mars, 2, INFORMATIONS_POPUP_MARS);
 t!(informations_popup_nasa, 2, INFORMATIONS_POPUP_NASA);
 t!(sandstorm, 2, SANDSTORM);
 t!(comet_strike, 2, COMET_STRIKE);
 t!(power_failure, 2, POWER_FAILURE);
-t!(resource_name, 3, RESOURCE_NAME);
+t!(seismic_tremor, 2, SEISMIC_TREMOR);
+t!(fire, 2, FIRE);
+t!(storm_damage, 2, STORM_DAMAGE);
+t!(resource_name, 6, RESOURCE_NAME);
+t!(scavenge, 2, SCAVENGE);
 
 t!(button_info => BUTTON_INFO);
 t!(winning_text => WINNING_TEXT);
+t!(chain_averted => CHAIN_AVERTED);
+t!(forecast => FORECAST);
+t!(tutorial_title => TUTORIAL_TITLE);
+t!(room_pressure => ROOM_PRESSURE);
+t!(suit_oxygen => SUIT_OXYGEN);
+t!(carry_weight => CARRY_WEIGHT);
 t!(additional_info_string => ADDITIONAL_INFO_STRING);
 t!(resume_error_string => RESUME_ERROR_STRING);
 t!(air_string => AIR_STRING);
 t!(energy_string => ENERGY_STRING);
 t!(air_and_energy_string => AIR_AND_ENERGY_STRING);
+t!(injury_string => INJURY_STRING);
+t!(hunger_string => HUNGER_STRING);
+t!(thirst_string => THIRST_STRING);
 t!(death_reason_string => DEATH_REASON_STRING);
 t!(intro_text => INTRO_TEXT);
 t!(tutorial_text => TUTORIAL_TEXT);
-t!(send_msg_failure => SEND_MSG_FAILURE);
+t!(handbook_close => HANDBOOK_CLOSE);
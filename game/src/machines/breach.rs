@@ -0,0 +1,121 @@
+//! This File contains the hull breaches, holes in the wall of the station which leak oxygen and energy
+use crate::backend::constants::{
+    gen_machine, machine_size, ObjectId, BREACH_GROWTH_TIME, BREACH_LEAK, BREACH_MAX_SEVERITY,
+};
+use crate::backend::gamestate::GameState;
+use crate::backend::utils::is_colliding;
+use crate::game_core::resources::Resources;
+use crate::machines::machine::State;
+use ggez::graphics::Rect;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// A hull breach which grows the longer it stays unrepaired.
+/// The leak rate and the cost of the repair grow with the severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Breach {
+    /// The severity of the breach, starting at 1
+    pub(crate) severity: u8,
+    /// Contains the amount of ticks since the breach opened or last grew
+    age: u32,
+}
+
+impl Breach {
+    /// Creates a new `Breach`, the severity is clamped to the valid range
+    /// # Arguments
+    /// * `severity` - The severity the breach starts with
+    pub(crate) fn new(severity: u8) -> Self {
+        Self {
+            severity: severity.clamp(1, BREACH_MAX_SEVERITY),
+            age: 0,
+        }
+    }
+
+    /// Returns the change rate of the player's resources caused by this breach
+    pub(crate) fn leak(self) -> Resources<i16> {
+        BREACH_LEAK * i16::from(self.severity)
+    }
+
+    /// Ages the breach by one tick and lets it grow once it is old enough
+    /// # Returns
+    /// * `bool` - true if the severity increased in this tick
+    pub(crate) fn tick(&mut self) -> bool {
+        if self.severity >= BREACH_MAX_SEVERITY {
+            return false;
+        }
+        self.age += 1;
+        if self.age < BREACH_GROWTH_TIME {
+            return false;
+        }
+        self.age = 0;
+        self.severity += 1;
+        true
+    }
+}
+
+impl GameState {
+    /// Returns the amount of breaches that are currently open
+    pub(crate) fn open_breaches(&self) -> usize {
+        self.machines
+            .iter()
+            .filter(|machine| machine.id.is_hole())
+            .count()
+    }
+
+    /// Returns all positions on the walls of the map where a new breach could open right now.
+    /// A position is free if it neither overlaps a machine, another breach nor the player.
+    pub(crate) fn free_breach_positions(&self) -> Vec<(f32, f32)> {
+        let (w, h) = machine_size(ObjectId::Hole);
+        self.map()
+            .breach_positions
+            .iter()
+            .copied()
+            .filter(|&(x, y)| {
+                let area = Rect::new(x, y, w, h);
+                !self.machines.iter().any(|m| m.hitbox.overlaps(&area))
+                    && !is_colliding(self.player.position, &area)
+            })
+            .collect()
+    }
+
+    /// Opens a new breach at a random free position on the walls.
+    /// Nothing happens if the maximum amount of breaches of the difficulty is reached.
+    /// # Arguments
+    /// * `severity` - The severity the breach starts with
+    /// # Returns
+    /// * `bool` - true if a breach was opened
+    pub(crate) fn spawn_breach(&mut self, severity: u8) -> bool {
+        if self.open_breaches() >= self.difficulty.max_breaches() {
+            info!("No breach opened, the maximum amount of breaches is reached");
+            return false;
+        }
+        let positions = self.free_breach_positions();
+        if positions.is_empty() {
+            return false;
+        }
+        let position = positions[fastrand::usize(..positions.len())];
+        info!(
+            "Opening breach with severity {} at {:?}",
+            severity, position
+        );
+        let mut machine = gen_machine(ObjectId::Hole, position, self.lng);
+        self.init_machine(&mut machine);
+        machine.open_breach(Breach::new(severity));
+        self.machines.push(machine);
+        self.update_collision();
+        true
+    }
+
+    /// Removes all breaches that have been repaired.
+    /// Closes the trade panel if it belonged to a removed breach, since the indices of the machines change.
+    pub(crate) fn update_breaches(&mut self) {
+        let before = self.machines.len();
+        self.machines
+            .retain(|machine| !machine.id.is_hole() || machine.state == State::Running);
+        if self.machines.len() != before {
+            info!("{} breach(es) repaired", before - self.machines.len());
+            self.trade_panel = None;
+            self.update_collision();
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! This File contains the `BuildMode`, which lets the player place new machines in the station
+use crate::backend::constants::{gen_blueprints, gen_machine, machine_size, PLAYER_ICON_SIZE};
+use crate::backend::gamestate::GameState;
+use crate::backend::rlcolor::RLColor;
+use crate::backend::screen::{Popup, ScreenCommand, StackCommand};
+use crate::backend::utils::get_draw_params;
+use crate::languages::{build_mode, trade_conflict_popup, trade_panel};
+use crate::machines::machine::State;
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawMode, Mesh, Rect, Text, TextFragment};
+use ggez::Context;
+use tracing::info;
+
+/// Distance between the player and the machine that is about to be placed
+const PLACEMENT_DISTANCE: f32 = 20.;
+
+/// The build mode lets the player spend items to place a new machine next to them.
+/// The machine is placed to the right of the player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuildMode {
+    /// Index of the selected blueprint
+    pub(crate) selected: usize,
+}
+
+impl BuildMode {
+    /// Selects the next blueprint, wraps around at the end of the list
+    /// # Arguments
+    /// * `blueprint_count` - The amount of available blueprints
+    pub(crate) fn select_next(&mut self, blueprint_count: usize) {
+        if blueprint_count > 0 {
+            self.selected = (self.selected + 1) % blueprint_count;
+        }
+    }
+
+    /// Returns the area the selected machine would occupy if it was placed now
+    /// # Arguments
+    /// * `gamestate` - The gamestate, used to get the player position and the blueprints
+    pub(crate) fn placement(self, gamestate: &GameState) -> Rect {
+        let (id, _) = &gen_blueprints(gamestate.lng)[self.selected];
+        let (w, h) = machine_size(*id);
+        Rect {
+            x: gamestate.player.position.0 + PLAYER_ICON_SIZE.0 + PLACEMENT_DISTANCE,
+            y: gamestate.player.position.1 + PLAYER_ICON_SIZE.1 - h,
+            w,
+            h,
+        }
+    }
+
+    /// Draws the outline of the machine that is about to be placed together with its cost
+    /// # Arguments
+    /// * `gamestate` - The gamestate, used to validate the placement
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    /// * `ctx` - The `Context` of the game
+    /// # Returns
+    /// * `RLResult` - validates if the drawing was successful
+    pub(crate) fn draw(
+        self,
+        gamestate: &GameState,
+        canvas: &mut Canvas,
+        scale: Vec2,
+        ctx: &mut Context,
+    ) -> RLResult {
+        let lng = gamestate.lng;
+        let (id, cost) = &gen_blueprints(lng)[self.selected];
+        let area = self.placement(gamestate);
+        let color = if gamestate.is_valid_placement(&area) {
+            RLColor::STATUS_GREEN
+        } else {
+            RLColor::STATUS_RED
+        };
+        let outline = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(0., 0., area.w, area.h),
+            Color { a: 0.4, ..color },
+        )?;
+        draw!(canvas, &outline, Vec2::new(area.x, area.y), scale);
+
+        let cost = cost
+            .iter()
+            .filter(|(_, amount)| *amount > 0)
+            .map(|(item, amount)| format!("{amount}x {}", item.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut text = Text::new(
+            TextFragment::new(format!(
+                "{}: {}\n{}: {cost}\n{}",
+                build_mode(lng)[0],
+                id.t(lng),
+                trade_panel(lng)[0],
+                build_mode(lng)[1]
+            ))
+            .color(RLColor::BLACK),
+        );
+        text.set_scale(22.);
+        draw!(canvas, &text, Vec2::new(820., 20.), scale);
+        Ok(())
+    }
+}
+
+impl GameState {
+    /// Places the machine selected in the build mode next to the player.
+    /// Informs the player with a `Popup` if items are missing or there is not enough space.
+    /// # Returns
+    /// * `RLResult` - validates if the machine was built successfully
+    pub(crate) fn build_machine(&mut self) -> RLResult {
+        let Some(mode) = self.build_mode else {
+            return Ok(());
+        };
+        let lng = self.lng;
+        let (id, cost) = gen_blueprints(lng).swap_remove(mode.selected);
+        let area = mode.placement(self);
+
+        let missing = self.player.missing_items(&cost);
+        let message = if !missing.is_empty() {
+            let missing_items = missing
+                .iter()
+                .map(|(item, amount)| format!("*{amount} {}", item.name))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Some(format!("{}\n{missing_items}", trade_conflict_popup(lng)[0]))
+        } else if !self.is_valid_placement(&area) {
+            Some(build_mode(lng)[2].to_string())
+        } else {
+            None
+        };
+        if let Some(message) = message {
+            self.get_screen_sender()?
+                .send(StackCommand::Screen(ScreenCommand::Popup(Popup::info(
+                    message,
+                ))))?;
+            return Ok(());
+        }
+
+        info!("Building {:?} at {:?}", id, area);
+        for (item, amount) in &cost {
+            self.player.add_item(item, -amount);
+        }
+        let mut machine = gen_machine(id, (area.x, area.y), lng);
+        machine.state = State::Idle;
+        self.init_machine(&mut machine);
+        self.machines.push(machine);
+        self.update_collision();
+        Ok(())
+    }
+}
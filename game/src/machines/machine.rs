@@ -6,11 +6,12 @@ use crate::backend::screen::{Popup, ScreenCommand, StackCommand};
 use crate::backend::utils::is_colliding;
 use crate::game_core::item::Item;
 use crate::game_core::player::Player;
-use crate::game_core::resources::Resources;
-use crate::languages::{trade_conflict_popup, Lang};
+use crate::game_core::resources::{ResourceKind, Resources};
+use crate::languages::{super_glue, trade_conflict_popup, Lang};
+use crate::machines::breach::Breach;
 use crate::machines::machine::State::{Broken, Idle, Running};
 use crate::machines::machine_sprite::MachineSprite;
-use crate::machines::trade::Trade;
+use crate::machines::trade::{Trade, TradeEffect};
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::sync::mpsc::Sender;
@@ -19,8 +20,9 @@ use crate::RLResult;
 use ggez::graphics::{Color, Image, Rect};
 use tracing::info;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum State {
+    #[default]
     Broken,
     Idle,
     Running,
@@ -63,9 +65,24 @@ pub struct Machine {
     /// Contains the last trade with a timer, Is uses to get information about the trade after the timer runs out
     last_trade: Trade,
     /// Denotes what amount of Resources is consumed and or produced as long as the Machine is in state running
-    running_resources: Resources<i16>,
+    pub(crate) running_resources: Resources<i16>,
+    /// The current upgrade tier of the Machine, starting at 1
+    #[serde(default = "Machine::first_tier")]
+    pub(crate) tier: u8,
+    /// Contains the `running_resources` of every tier above the first one
+    #[serde(default)]
+    tiers: Vec<Resources<i16>>,
+    /// Contains the breach if this Machine is a hole in the wall
+    #[serde(default)]
+    pub(crate) breach: Option<Breach>,
+    /// Contains the items the player deposited in this Machine, only used by storage lockers
+    #[serde(default)]
+    pub(crate) storage: Vec<(Item, i32)>,
     /// Contains the amount of tics until the timer rus out
     time_remaining: i16,
+    /// Contains the amount of tics the running trade takes, including the slowdown by status effects
+    #[serde(default)]
+    time_total: i16,
     /// Denotes weather the timer is running or not via 0 or 1 also used for calculations
     time_change: i16,
     #[serde(skip)]
@@ -109,7 +126,12 @@ impl Machine {
             trades,
             last_trade: Trade::default(),
             running_resources,
+            tier: Self::first_tier(),
+            tiers: vec![],
+            breach: None,
+            storage: vec![],
             time_remaining: 0,
+            time_total: 0,
             time_change: 0,
             sender: None,
             screen_sender: None,
@@ -127,27 +149,81 @@ impl Machine {
         Self::new(id, hit_box, trades, running_resources)
     }
 
+    /// Adds upgrade tiers to the Machine
+    /// # Arguments
+    /// * `tiers` - The `running_resources` of every tier above the first one
+    pub(crate) fn with_tiers(mut self, tiers: Vec<Resources<i16>>) -> Self {
+        self.tiers = tiers;
+        self
+    }
+
+    /// Returns the tier every Machine starts with
+    fn first_tier() -> u8 {
+        1
+    }
+
+    /// Returns the highest tier this Machine can be upgraded to
+    pub(crate) fn max_tier(&self) -> u8 {
+        u8::try_from(self.tiers.len() + 1).unwrap_or(u8::MAX)
+    }
+
     /// initialises the Maschine with the data that is not Serialize
     /// This funktion is required to be called before the firs draw call
     /// # Arguments
-    /// * `images` - A Slice of Images containing the sprites for this Machine
+    /// * `images` - The sprites for this Machine, one list of sprites for every tier that has its own sprites
     /// * `sender` - A sender of type `Sender<GameCommand>`
     /// * `screen_sender` - A sender of type `Sender<StackCommand>`
     pub(crate) fn init(
         &mut self,
-        images: &[Image],
+        images: Vec<Vec<Image>>,
         sender: Sender<GameCommand>,
         screen_sender: Sender<StackCommand>,
     ) {
         self.sprite = Some(images.into());
         self.sender = Some(sender);
         self.screen_sender = Some(screen_sender);
-        if self.id.is_hole() {
-            // Constant (pos of hole)
-            if (self.hitbox.x - 780.).abs() < f32::EPSILON {
-                self.change_state_to(&Running);
-            } else {
-                self.change_state_to(&Idle);
+    }
+
+    /// Opens a breach in this Machine, which leaks resources until it is repaired
+    /// # Arguments
+    /// * `breach` - The breach to open
+    pub(crate) fn open_breach(&mut self, breach: Breach) {
+        self.running_resources = breach.leak();
+        self.breach = Some(breach);
+        for _ in 1..breach.severity {
+            self.increase_repair_cost();
+        }
+        self.change_state_to(&Running);
+    }
+
+    /// Lets the breach of this Machine grow if it is open long enough.
+    /// The change rate of the player's resources and the repair cost are adjusted to the new severity.
+    fn grow_breach(&mut self) -> RLResult {
+        let Some(breach) = self.breach.as_mut() else {
+            return Ok(());
+        };
+        if self.state != Running || !breach.tick() {
+            return Ok(());
+        }
+        let leak = breach.leak();
+        info!("Breach grew to severity {}", breach.severity);
+        self.sender
+            .as_ref()
+            .unwrap()
+            .send(GameCommand::ResourceChange(leak - self.running_resources))?;
+        self.running_resources = leak;
+        self.increase_repair_cost();
+        Ok(())
+    }
+
+    /// Increases the cost of every trade of this Machine by one super glue.
+    /// Used for breaches, which get harder to repair with every severity step.
+    fn increase_repair_cost(&mut self) {
+        // the image of an item is the same in every language
+        let glue = super_glue(Lang::En)[2];
+        for trade in &mut self.trades {
+            if let Some((_, amount)) = trade.cost.iter_mut().find(|(item, _)| item.img == glue) {
+                *amount += 1;
             }
         }
     }
@@ -156,7 +232,19 @@ impl Machine {
     /// # Returns
     /// * `&Image` - a reference to the graphic
     pub(crate) fn get_graphic(&self) -> &Image {
-        self.sprite.as_ref().unwrap().get(self.state.clone())
+        self.sprite
+            .as_ref()
+            .unwrap()
+            .get(self.state.clone(), self.tier)
+    }
+
+    /// Returns the color the sprite is tinted with.
+    /// Upgraded Machines without their own sprites are tinted to still look different.
+    /// # Returns
+    /// * `Option<Color>` - The tint or None if the sprite should be drawn as it is
+    pub(crate) fn get_tint(&self) -> Option<Color> {
+        let available_tier = self.sprite.as_ref()?.available_tier(self.tier);
+        (available_tier < self.tier).then_some(RLColor::TIER_TINT)
     }
 
     /// Calculates the Percentage of time remaining on the timer
@@ -165,8 +253,11 @@ impl Machine {
     pub(crate) fn get_time_percentage(&self) -> f32 {
         if self.last_trade.time_ticks == 0 {
             -1.0
-        } else {
+        } else if self.time_total == 0 {
+            // saves from before the slowdown did not store the duration
             f32::from(self.time_remaining) / f32::from(self.last_trade.time_ticks)
+        } else {
+            f32::from(self.time_remaining) / f32::from(self.time_total)
         }
     }
 
@@ -176,42 +267,80 @@ impl Machine {
     /// # Returns
     /// * `true` if the player collides with this Machine
     /// * `false` if the player does not collide with this Machine
-    pub(crate) fn is_interactable(&self, pos: (usize, usize)) -> bool {
+    pub(crate) fn is_interactable(&self, pos: (f32, f32)) -> bool {
         is_colliding(pos, &self.interaction_area)
     }
 
+    /// Returns all trades the player knows about, meaning all trades that are not locked.
+    /// A trade that exists for several states is only listed once, preferring the current state.
+    pub(crate) fn listed_trades(&self) -> Vec<&Trade> {
+        self.trades
+            .iter()
+            .filter(|trade| !trade.locked)
+            .filter(|trade| {
+                trade.initial_state == self.state
+                    || !self
+                        .trades
+                        .iter()
+                        .any(|t| t.id == trade.id && t.initial_state == self.state)
+            })
+            .collect()
+    }
+
+    /// Determines if a trade of this Machine can be chosen in the current state
+    /// # Arguments
+    /// * `trade` - The trade to check
+    /// # Returns
+    /// * `true` if the Machine is in the `initial_state` of the trade and, for withdrawals, stores the items
+    pub(crate) fn is_trade_available(&self, trade: &Trade) -> bool {
+        trade.id != TradeId::NoTrade
+            && !trade.locked
+            && trade.initial_state == self.state
+            && (self.tier < self.max_tier() || !trade.effects.contains(&TradeEffect::Upgrade))
+            && (!trade.effects.contains(&TradeEffect::Withdraw)
+                || trade
+                    .items_yield()
+                    .all(|(item, amount)| self.stored_amount(item) >= amount))
+    }
+
     /// Handel's the interaction of the Machine and the player
     /// # Arguments
+    /// * `trade_id` - The trade the player has chosen in the trade panel
     /// * `player` - of type `& Player` is a reference to the player
-    pub(crate) fn interact(&mut self, player: &Player, lng: Lang) -> RLResult {
-        // Check if there is a possible trade
-        let trade = match self.trades.iter().find(|t| t.initial_state == self.state) {
+    pub(crate) fn interact(&mut self, trade_id: TradeId, player: &Player, lng: Lang) -> RLResult {
+        // Check if the chosen trade is possible
+        let trade = match self
+            .trades
+            .iter()
+            .find(|t| t.id == trade_id && self.is_trade_available(t))
+        {
             Some(t) => t.clone(),
             None => return Ok(()),
         };
 
-        if trade.id == TradeId::NoTrade {
-            return Ok(());
-        }
-
         // Check if the player has energy (and its needed)
-        if player.resources.energy == 0 && self.running_resources.energy < 0 && self.id.is_hole() {
+        if player.resources[ResourceKind::Energy] == 0
+            && self.running_resources[ResourceKind::Energy] < 0
+            && self.id.is_hole()
+        {
             return Ok(());
         }
-        // dif = the different between items the player has and the cost of the trade
-        let dif = trade
-            .cost
-            .iter()
-            .map(|(item, demand)| (item, player.get_item_amount(item) - demand))
-            .filter(|(_item, dif)| *dif < 0)
-            .collect::<Vec<(&Item, i32)>>();
-        // If one item is not available in enough quantity inform the player and cancel the interaction
-        if dif.iter().any(|(_, demand)| *demand < 0) {
+        let missing = player.missing_items(&trade.cost);
+        let overflowing = player.overflowing_items(&trade.cost);
+        // If one item is not available in enough quantity or does not fit into the inventory
+        // inform the player and cancel the interaction
+        if !missing.is_empty() || !overflowing.is_empty() {
+            let (conflict, items) = if missing.is_empty() {
+                (trade_conflict_popup(lng)[1], overflowing)
+            } else {
+                (trade_conflict_popup(lng)[0], missing)
+            };
             let mut missing_items = String::new();
-            dif.iter()
-                .map(|(item, amount)| format!("*{} {}\n", amount * -1, item.name))
+            items
+                .iter()
+                .map(|(item, amount)| format!("*{amount} {}\n", item.name))
                 .for_each(|x| missing_items.push_str(&x));
-            let popup = Popup::info(format!("{}\n{missing_items}", trade_conflict_popup(lng)[0]));
+            let popup = Popup::info(format!("{conflict}\n{missing_items}"));
             info!(
                 "Popup for Trade conflict sent: Missing Items: {}",
                 missing_items
@@ -228,10 +357,8 @@ impl Machine {
 
         // Remove the cost of the trade from the players inventory by sending the demand to the AddItem GameCommand
         let items_cost = trade
-            .cost
-            .iter()
-            .filter(|(_, demand)| *demand >= 0)
-            .map(|(item, demand)| (item.clone(), -*demand))
+            .items_cost()
+            .map(|(item, demand)| (item.clone(), -demand))
             .collect::<Vec<(Item, i32)>>();
         self.sender
             .as_ref()
@@ -241,12 +368,21 @@ impl Machine {
         if trade.time_ticks == 0 {
             // this trade has no timer
             self.time_change = 0;
+            if !trade.return_after_timer {
+                self.change_state_to(&trade.resulting_state);
+            }
+            self.conclude_trade(&trade)?;
         } else {
             //this trade has a timer
             if self.time_remaining == 0 {
                 //if no timer is running set timer up
                 self.last_trade = trade.clone();
-                self.time_remaining = trade.time_ticks;
+                // status effects of the player can slow down trades
+                self.time_remaining = i16::try_from(
+                    i64::from(trade.time_ticks) * i64::from(player.trade_time_percent()) / 100,
+                )
+                .unwrap_or(i16::MAX);
+                self.time_total = self.time_remaining;
             }
             //start the timer
             self.time_change = 1;
@@ -259,8 +395,9 @@ impl Machine {
         Ok(())
     }
 
-    /// Handels the timer by being called every tick
+    /// Handels the timer and the growth of breaches by being called every tick
     pub(crate) fn tick(&mut self) -> RLResult {
+        self.grow_breach()?;
         self.time_remaining -= self.time_change;
         //if the timer has run out
         if self.time_remaining < 0 {
@@ -269,27 +406,103 @@ impl Machine {
             self.time_remaining = 0;
 
             if self.last_trade.return_after_timer {
-                // handel edge case for wining the game
-                if self.last_trade.id == TradeId::EmergencySignalOff {
-                    return Ok(self.sender.as_ref().unwrap().send(GameCommand::Winning)?);
-                }
                 self.change_state_to(&self.last_trade.initial_state.clone());
             } else {
                 self.change_state_to(&self.last_trade.resulting_state.clone());
             }
-            // After Trade ended, send the GameCommand AddItems to add the earning Items to the players inventory
             let trade = self.last_trade.clone();
-            let items = trade
-                .cost
-                .iter()
-                .filter(|(_, demand)| *demand < 0)
-                .map(|(item, demand)| (item.clone(), -*demand))
-                .collect::<Vec<(Item, i32)>>();
+            self.conclude_trade(&trade)?;
+        }
+        Ok(())
+    }
+
+    /// Sends the outcome of a concluded trade to the `GameState`.
+    /// This adds the earned items to the players inventory and applies the effects of the trade.
+    /// # Arguments
+    /// * `trade` - The trade that concluded
+    fn conclude_trade(&mut self, trade: &Trade) -> RLResult {
+        let sender = self.sender.clone().unwrap();
+        let items = trade
+            .items_yield()
+            .map(|(item, amount)| (item.clone(), amount))
+            .collect::<Vec<(Item, i32)>>();
+        if !items.is_empty() {
+            sender.send(GameCommand::AddItems(items))?;
+        }
+        sender.send(GameCommand::TradeConcluded(trade.id))?;
+        for effect in &trade.effects {
+            info!("Trade {:?} concluded with effect {:?}", trade.id, effect);
+            match effect.to_command() {
+                Some(command) => sender.send(command)?,
+                None => self.apply_effect(*effect, trade)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies an effect of a trade that only concerns this Machine
+    /// # Arguments
+    /// * `effect` - The effect to apply
+    /// * `trade` - The trade the effect belongs to
+    fn apply_effect(&mut self, effect: TradeEffect, trade: &Trade) -> RLResult {
+        match effect {
+            TradeEffect::Upgrade => self.upgrade()?,
+            TradeEffect::Deposit => {
+                for (item, amount) in trade.items_cost() {
+                    self.store(item, amount);
+                }
+            }
+            TradeEffect::Withdraw => {
+                for (item, amount) in trade.items_yield() {
+                    self.store(item, -amount);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the amount of the given item stored in this Machine
+    /// # Arguments
+    /// * `item` - The item to get the amount of
+    pub(crate) fn stored_amount(&self, item: &Item) -> i32 {
+        self.storage
+            .iter()
+            .find(|(stored, _)| stored.img == item.img)
+            .map_or(0, |(_, amount)| *amount)
+    }
+
+    /// Changes the amount of an item stored in this Machine, the amount never goes below zero
+    /// # Arguments
+    /// * `item` - The item to store or take out
+    /// * `amount_change` - The amount that is stored, negative if it is taken out
+    fn store(&mut self, item: &Item, amount_change: i32) {
+        match self
+            .storage
+            .iter_mut()
+            .find(|(stored, _)| stored.img == item.img)
+        {
+            Some((_, amount)) => *amount = (*amount + amount_change).max(0),
+            None => self.storage.push((item.clone(), amount_change.max(0))),
+        }
+        self.storage.retain(|(_, amount)| *amount > 0);
+    }
+
+    /// Upgrades the Machine to the next tier if there is one.
+    /// If the Machine is running the change rate of the player's resources is adjusted to the new tier.
+    pub(crate) fn upgrade(&mut self) -> RLResult {
+        let Some(next) = self.tiers.get(usize::from(self.tier) - 1).copied() else {
+            return Ok(());
+        };
+        if self.state == Running {
             self.sender
                 .as_ref()
                 .unwrap()
-                .send(GameCommand::AddItems(items))?;
+                .send(GameCommand::ResourceChange(next - self.running_resources))?;
         }
+        self.running_resources = next;
+        self.tier += 1;
+        info!("Machine {:?} upgraded to tier {}", self.id, self.tier);
         Ok(())
     }
 
@@ -307,7 +520,7 @@ impl Machine {
     }
     /// A helper funktion to disable every funktion in case there is no energy in the system
     pub(crate) fn no_energy(&mut self) {
-        if self.running_resources.energy < 0 && !self.id.is_hole() {
+        if self.running_resources[ResourceKind::Energy] < 0 && !self.id.is_hole() {
             // If there is no energy available but this machine needs some, stop this machine.
             if self.state == Running {
                 self.change_state_to(&Idle);
@@ -321,31 +534,20 @@ impl Machine {
     /// * `before` - the current state of the system
     /// * `after` - the state that it will be in after the change is complete
     fn invoke_state_change(&self, before: &State, after: &State) {
+        // Machines which are not initialized yet belong to the start of a scenario,
+        // which sets up the resource changes of the player itself
+        let Some(sender) = self.sender.as_ref() else {
+            return;
+        };
         match (before, after) {
-            (Broken, Idle) => {
-                let _e = self.sender.as_ref().unwrap().send(GameCommand::Milestone);
-            }
-            (Idle, Broken) => {}
+            (Broken, Idle) | (Idle, Broken) => {}
             (Broken | Idle, Running) => {
-                let _e = self
-                    .sender
-                    .as_ref()
-                    .unwrap()
-                    .send(GameCommand::ResourceChange(self.running_resources));
-                let _e = self.sender.as_ref().unwrap().send(GameCommand::Milestone);
+                let _e = sender.send(GameCommand::ResourceChange(self.running_resources));
             }
             (Running, Broken | Idle) => {
-                let _e = self
-                    .sender
-                    .as_ref()
-                    .unwrap()
-                    .send(GameCommand::ResourceChange(
-                        Resources {
-                            oxygen: 0,
-                            energy: 0,
-                            life: 0,
-                        } - self.running_resources,
-                    ));
+                let _e = sender.send(GameCommand::ResourceChange(
+                    Resources::default() - self.running_resources,
+                ));
             }
             _ => {
                 info!(
@@ -357,3 +559,172 @@ impl Machine {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::constants::gen_machine;
+    use crate::game_core::player::gen_inventory;
+    use crate::game_core::status_effect::StatusKind;
+    use std::path::Path;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_instant_trade() {
+        // Trades without a timer conclude as soon as they are chosen
+        let trade = Trade::new(
+            TradeId::FuelingPowerGenerator,
+            0,
+            Idle,
+            Running,
+            false,
+            gen_inventory(0, -1, 0, 0, Lang::En),
+        )
+        .with_effects(vec![TradeEffect::Heal(10)]);
+        let mut machine = Machine::new(
+            ObjectId::PowerGenerator,
+            Rect::new(0., 0., 100., 100.),
+            vec![trade],
+            Resources::default(),
+        );
+        machine.state = Idle;
+        let (sender, receiver) = channel();
+        machine.sender = Some(sender);
+        machine
+            .interact(
+                TradeId::FuelingPowerGenerator,
+                &Player::new(Lang::En),
+                Lang::En,
+            )
+            .unwrap();
+        assert_eq!(machine.state, Running);
+        let commands = receiver.try_iter().collect::<Vec<_>>();
+        assert!(commands.iter().any(|command| matches!(
+            command,
+            GameCommand::TradeConcluded(TradeId::FuelingPowerGenerator)
+        )));
+        assert!(commands
+            .iter()
+            .any(|command| matches!(command, GameCommand::Heal(10))));
+        assert!(commands.iter().any(|command| matches!(
+            command,
+            GameCommand::AddItems(items) if items.iter().any(|(_, amount)| *amount == 1)
+        )));
+    }
+
+    #[test]
+    fn test_slowed_trade_progress() {
+        // Status effects slow down trades, the progress still starts at 100% and runs down to 0%
+        let trade = Trade::new(
+            TradeId::FuelingPowerGenerator,
+            100,
+            Idle,
+            Running,
+            false,
+            gen_inventory(0, 0, 0, 0, Lang::En),
+        );
+        let mut machine = Machine::new(
+            ObjectId::PowerGenerator,
+            Rect::new(0., 0., 100., 100.),
+            vec![trade],
+            Resources::default(),
+        );
+        machine.state = Idle;
+        let (sender, _receiver) = channel();
+        machine.sender = Some(sender);
+        let mut player = Player::new(Lang::En);
+        player.add_status(StatusKind::Exhaustion, 60);
+        machine
+            .interact(TradeId::FuelingPowerGenerator, &player, Lang::En)
+            .unwrap();
+        assert!((machine.get_time_percentage() - 1.0).abs() < f32::EPSILON);
+        while machine.time_change != 0 {
+            assert!((0.0..=1.0).contains(&machine.get_time_percentage()));
+            machine.tick().unwrap();
+        }
+        assert_eq!(machine.state, Running);
+    }
+
+    #[test]
+    fn test_storage_independent_of_language() {
+        // Items stored in one language can be taken out after switching the language
+        let mut machine = gen_machine(ObjectId::StorageCrate, (0., 0.), Lang::De);
+        let (petrol_de, _) = gen_inventory(0, 1, 0, 0, Lang::De).remove(1);
+        let (petrol_en, _) = gen_inventory(0, 1, 0, 0, Lang::En).remove(1);
+        assert_ne!(petrol_de.name, petrol_en.name);
+        machine.store(&petrol_de, 2);
+        machine.store(&petrol_en, 1);
+        assert_eq!(machine.storage.len(), 1);
+        assert_eq!(machine.stored_amount(&petrol_en), 3);
+    }
+
+    #[test]
+    fn test_tier_sprites() {
+        // Every tier of a machine has its own sprites
+        for id in ObjectId::ALL {
+            let machine = gen_machine(id, (0., 0.), Lang::De);
+            let name = id.t(Lang::De);
+            for tier in 2..=machine.max_tier() {
+                for state in ["Broken", "Idle", "Running"] {
+                    let path = format!("../assets/{name}_Tier{tier}_{state}.png");
+                    assert!(Path::new(&path).exists(), "{path}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_repair_cost_of_breach() {
+        // Only the super glue gets more expensive, wherever it is in the cost
+        let cost = gen_inventory(2, 1, 0, 0, Lang::En)
+            .into_iter()
+            .rev()
+            .collect();
+        let mut machine = Machine::new(
+            ObjectId::Hole,
+            Rect::new(0., 0., 100., 100.),
+            vec![Trade::new(
+                TradeId::RepairHole,
+                100,
+                Running,
+                Idle,
+                false,
+                cost,
+            )],
+            Resources::default(),
+        );
+        let (sender, _receiver) = channel();
+        machine.sender = Some(sender);
+        machine.open_breach(Breach::new(3));
+        let glue = super_glue(Lang::En)[2];
+        for (item, amount) in &machine.trades[0].cost {
+            let expected = match item.img.as_str() {
+                img if img == glue => 4,
+                "Benzin.png" => 1,
+                _ => 0,
+            };
+            assert_eq!(*amount, expected, "{}", item.img);
+        }
+    }
+
+    #[test]
+    fn test_seal_airlock_in_every_state() {
+        // The airlock can be sealed whatever state the oxygen generator is in
+        let mut machine = gen_machine(ObjectId::OxygenGenerator, (0., 0.), Lang::En);
+        machine
+            .trades
+            .iter_mut()
+            .filter(|trade| trade.id == TradeId::SealAirlock)
+            .for_each(|trade| trade.locked = false);
+        for state in [Broken, Idle, Running] {
+            machine.state = state;
+            let listed = machine
+                .listed_trades()
+                .into_iter()
+                .filter(|trade| trade.id == TradeId::SealAirlock)
+                .collect::<Vec<_>>();
+            assert_eq!(listed.len(), 1);
+            assert!(machine.is_trade_available(listed[0]));
+        }
+    }
+}
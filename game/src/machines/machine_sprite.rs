@@ -3,17 +3,26 @@ use crate::machines::machine::State;
 use ggez::graphics::Image;
 
 #[derive(Debug, Clone)]
-/// Contains all the sprites associated with one Machine
-pub struct MachineSprite(Vec<Image>);
-impl From<&[Image]> for MachineSprite {
-    fn from(value: &[Image]) -> Self {
-        Self(value.to_vec())
+/// Contains all the sprites associated with one Machine.
+/// The first entry contains the sprites of the first tier, every following entry the sprites of the next tier.
+pub struct MachineSprite(Vec<Vec<Image>>);
+impl From<Vec<Vec<Image>>> for MachineSprite {
+    fn from(value: Vec<Vec<Image>>) -> Self {
+        Self(value)
     }
 }
 
 impl MachineSprite {
-    ///Grabs the correct Image depending on the passed in State
-    pub fn get(&self, state: State) -> &Image {
-        self.0.get(state as usize).unwrap_or(&self.0[0])
+    ///Grabs the correct Image depending on the passed in State and tier
+    /// If there are no sprites for the tier the sprites of the highest available tier are used
+    pub fn get(&self, state: State, tier: u8) -> &Image {
+        let tier = self.available_tier(tier);
+        let sprites = &self.0[usize::from(tier) - 1];
+        sprites.get(state as usize).unwrap_or(&sprites[0])
+    }
+
+    /// Returns the highest tier up to `tier` which has its own sprites
+    pub fn available_tier(&self, tier: u8) -> u8 {
+        tier.clamp(1, u8::try_from(self.0.len()).unwrap_or(u8::MAX))
     }
 }
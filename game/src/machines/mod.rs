@@ -1,3 +1,6 @@
+pub(crate) mod breach;
+pub(crate) mod build_mode;
 pub(crate) mod machine;
 pub(crate) mod machine_sprite;
 pub(crate) mod trade;
+pub(crate) mod trade_panel;
@@ -1,9 +1,50 @@
 //! This File contains the structure `Trade`
-use crate::backend::constants::TradeId;
+use crate::backend::constants::{EventId, TradeId};
+use crate::backend::gamestate::GameCommand;
 use crate::game_core::item::Item;
+use crate::game_core::resources::Resources;
 use crate::machines::machine::State;
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// An additional outcome of a trade which takes effect once the trade concluded
+pub enum TradeEffect {
+    /// Changes the change rate of the player's resources
+    ResourceChange(Resources<i16>),
+    /// Triggers the event with the given id
+    TriggerEvent(EventId),
+    /// Unlocks every trade with the given id
+    UnlockTrade(TradeId),
+    /// Restores the given amount of life of the player
+    Heal(u16),
+    /// Upgrades the machine to its next tier
+    Upgrade,
+    /// Weakens the active event with the given id by the given percentage
+    MitigateEvent(EventId, u8),
+    /// Stores the items the player paid in the machine
+    Deposit,
+    /// Takes the items the player receives out of the machine, the trade is only available if they are stored
+    Withdraw,
+}
+
+impl TradeEffect {
+    /// Returns the `GameCommand` which applies this effect
+    /// # Returns
+    /// * `Option<GameCommand>` - The command or None if the effect only concerns the machine itself
+    pub(crate) fn to_command(self) -> Option<GameCommand> {
+        match self {
+            TradeEffect::ResourceChange(resources) => Some(GameCommand::ResourceChange(resources)),
+            TradeEffect::TriggerEvent(event) => Some(GameCommand::TriggerEvent(event)),
+            TradeEffect::UnlockTrade(trade) => Some(GameCommand::UnlockTrade(trade)),
+            TradeEffect::Heal(amount) => Some(GameCommand::Heal(amount)),
+            TradeEffect::MitigateEvent(event, percent) => {
+                Some(GameCommand::MitigateEvent(event, percent))
+            }
+            TradeEffect::Upgrade | TradeEffect::Deposit | TradeEffect::Withdraw => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 /// A trade is one interaction option with a machine
 /// This means that repairing, starting or pausing a machine are all trades
@@ -27,6 +68,12 @@ pub struct Trade {
     /// * Positive amount means the Player will **lose** these items.
     /// * Negative amount means the Player will **gain** these items.
     pub(crate) cost: Vec<(Item, i32)>,
+    /// Contains the effects that take place after the trade concluded
+    #[serde(default)]
+    pub(crate) effects: Vec<TradeEffect>,
+    /// A locked trade is not available until it is unlocked by a `TradeEffect::UnlockTrade`
+    #[serde(default)]
+    pub(crate) locked: bool,
 }
 
 impl Default for Trade {
@@ -40,6 +87,8 @@ impl Default for Trade {
             resulting_state: State::Running,
             return_after_timer: false,
             cost: vec![],
+            effects: vec![],
+            locked: false,
         }
     }
 }
@@ -61,6 +110,38 @@ impl Trade {
             resulting_state,
             return_after_timer,
             cost,
+            effects: vec![],
+            locked: false,
         }
     }
+
+    /// Adds effects which take place after the trade concluded
+    /// # Arguments
+    /// * `effects` - The effects of the trade
+    pub fn with_effects(mut self, effects: Vec<TradeEffect>) -> Self {
+        self.effects = effects;
+        self
+    }
+
+    /// Locks the trade, it is hidden until it gets unlocked
+    pub fn locked(mut self) -> Self {
+        self.locked = true;
+        self
+    }
+
+    /// Returns the items the player pays for this trade
+    pub(crate) fn items_cost(&self) -> impl Iterator<Item = (&Item, i32)> {
+        self.cost
+            .iter()
+            .filter(|(_, amount)| *amount > 0)
+            .map(|(item, amount)| (item, *amount))
+    }
+
+    /// Returns the items the player receives after this trade concluded
+    pub(crate) fn items_yield(&self) -> impl Iterator<Item = (&Item, i32)> {
+        self.cost
+            .iter()
+            .filter(|(_, amount)| *amount < 0)
+            .map(|(item, amount)| (item, -*amount))
+    }
 }
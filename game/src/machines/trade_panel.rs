@@ -0,0 +1,199 @@
+//! This File contains the `TradePanel`, the overlay used to choose one of the trades of a machine
+use crate::backend::constants::DESIRED_FPS;
+use crate::backend::rlcolor::RLColor;
+use crate::backend::utils::get_draw_params;
+use crate::game_core::item::Item;
+use crate::game_core::player::Player;
+use crate::languages::{trade_panel, Lang};
+use crate::machines::machine::Machine;
+use crate::machines::trade::Trade;
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawMode, Mesh, Rect, Text, TextFragment};
+use ggez::Context;
+
+/// Position of the top left corner of the panel
+const PANEL_POSITION: (f32, f32) = (460., 250.);
+/// Width of the panel
+const PANEL_WIDTH: f32 = 1000.;
+/// Height of one trade entry inside the panel
+const ENTRY_HEIGHT: f32 = 90.;
+
+/// Overlay which lists every trade of the machine the player interacts with.
+/// It shows the cost, the yield and the duration of each trade and whether the player can afford it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradePanel {
+    /// Index of the machine inside `GameState::machines`
+    pub(crate) machine: usize,
+    /// Index of the currently selected trade
+    pub(crate) selected: usize,
+}
+
+impl TradePanel {
+    /// Creates a new `TradePanel` for the machine with the given index
+    pub(crate) fn new(machine: usize) -> Self {
+        Self {
+            machine,
+            selected: 0,
+        }
+    }
+
+    /// Moves the selection one entry down, wraps around at the end of the list
+    /// # Arguments
+    /// * `trade_count` - The amount of trades listed in the panel
+    pub(crate) fn select_next(&mut self, trade_count: usize) {
+        if trade_count > 0 {
+            self.selected = (self.selected + 1) % trade_count;
+        }
+    }
+
+    /// Moves the selection one entry up, wraps around at the beginning of the list
+    /// # Arguments
+    /// * `trade_count` - The amount of trades listed in the panel
+    pub(crate) fn select_previous(&mut self, trade_count: usize) {
+        if trade_count > 0 {
+            self.selected = (self.selected + trade_count - 1) % trade_count;
+        }
+    }
+
+    /// Formats a list of items as `2x SuperGlue, 1x Benzin` or `-` if the list is empty
+    fn format_items<'a>(items: impl Iterator<Item = (&'a Item, i32)>) -> String {
+        let text = items
+            .map(|(item, amount)| format!("{amount}x {}", item.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if text.is_empty() {
+            "-".to_string()
+        } else {
+            text
+        }
+    }
+
+    /// Builds the description line of one trade
+    /// # Returns
+    /// * `(String, Color)` - The text and the color it should be drawn in
+    fn describe(machine: &Machine, trade: &Trade, player: &Player, lng: Lang) -> (String, Color) {
+        let text = trade_panel(lng);
+        let description = format!(
+            "{}: {}   {}: {}   {}: {:.1}s",
+            text[0],
+            Self::format_items(trade.items_cost()),
+            text[1],
+            Self::format_items(trade.items_yield()),
+            text[2],
+            f32::from(trade.time_ticks) / DESIRED_FPS as f32
+        );
+        if !machine.is_trade_available(trade) {
+            return (format!("{description}\n{}", text[4]), RLColor::DARK_GREY);
+        }
+        let missing = player.missing_items(&trade.cost);
+        if missing.is_empty() {
+            (description, RLColor::BLACK)
+        } else {
+            let missing = Self::format_items(missing.iter().map(|(item, amount)| (item, *amount)));
+            (
+                format!("{description}\n{}: {missing}", text[3]),
+                RLColor::RED,
+            )
+        }
+    }
+
+    /// Draws the panel with all trades of the given machine on top of the game
+    /// # Arguments
+    /// * `machine` - The machine the panel belongs to
+    /// * `player` - The player, used to check which trades are affordable
+    /// * `canvas` - The canvas to draw on
+    /// * `scale` - The scale of the canvas
+    /// * `ctx` - The `Context` of the game
+    /// * `lng` - The current language
+    /// # Returns
+    /// * `RLResult` - validates if the drawing was successful
+    pub(crate) fn draw(
+        &self,
+        machine: &Machine,
+        player: &Player,
+        canvas: &mut Canvas,
+        scale: Vec2,
+        ctx: &mut Context,
+        lng: Lang,
+    ) -> RLResult {
+        let trades = machine.listed_trades();
+        let height = 110. + ENTRY_HEIGHT * trades.len() as f32;
+        let background = Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(0., 0., PANEL_WIDTH, height),
+            10.,
+            RLColor::GREY,
+        )?;
+        let border = Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::stroke(4.),
+            Rect::new(0., 0., PANEL_WIDTH, height),
+            10.,
+            RLColor::BLACK,
+        )?;
+        let position = Vec2::new(PANEL_POSITION.0, PANEL_POSITION.1);
+        draw!(canvas, &background, position, scale);
+        draw!(canvas, &border, position, scale);
+
+        let mut title = Text::new(
+            TextFragment::new(format!(
+                "{} ({} {}/{})",
+                machine.id.t(lng),
+                trade_panel(lng)[6],
+                machine.tier,
+                machine.max_tier()
+            ))
+            .color(RLColor::BLACK),
+        );
+        title.set_scale(36.);
+        draw!(canvas, &title, position + Vec2::new(20., 15.), scale);
+        if !machine.storage.is_empty() {
+            let stored =
+                Self::format_items(machine.storage.iter().map(|(item, amount)| (item, *amount)));
+            let mut stored = Text::new(
+                TextFragment::new(format!("{}: {stored}", trade_panel(lng)[7]))
+                    .color(RLColor::BLACK),
+            );
+            stored.set_scale(20.);
+            draw!(
+                canvas,
+                &stored,
+                position + Vec2::new(PANEL_WIDTH / 2., 25.),
+                scale
+            );
+        }
+
+        for (i, trade) in trades.into_iter().enumerate() {
+            let entry_position = position + Vec2::new(20., 65. + ENTRY_HEIGHT * i as f32);
+            if i == self.selected {
+                let highlight = Mesh::new_rounded_rectangle(
+                    ctx,
+                    DrawMode::fill(),
+                    Rect::new(-10., -5., PANEL_WIDTH - 20., ENTRY_HEIGHT - 5.),
+                    5.,
+                    RLColor::LIGHT_GREY,
+                )?;
+                draw!(canvas, &highlight, entry_position, scale);
+            }
+            let (description, color) = Self::describe(machine, trade, player, lng);
+            let mut name = Text::new(TextFragment::new(trade.id.t(lng)).color(color));
+            name.set_scale(28.);
+            draw!(canvas, &name, entry_position, scale);
+            let mut details = Text::new(TextFragment::new(description).color(color));
+            details.set_scale(20.);
+            draw!(canvas, &details, entry_position + Vec2::new(0., 32.), scale);
+        }
+
+        let mut controls = Text::new(TextFragment::new(trade_panel(lng)[5]).color(RLColor::BLACK));
+        controls.set_scale(20.);
+        draw!(
+            canvas,
+            &controls,
+            position + Vec2::new(20., height - 35.),
+            scale
+        );
+        Ok(())
+    }
+}
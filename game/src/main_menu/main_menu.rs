@@ -1,3 +1,4 @@
+use crate::backend::constants::Difficulty;
 use crate::backend::rlcolor::RLColor;
 use crate::backend::{
     gamestate::GameState,
@@ -5,10 +6,10 @@ use crate::backend::{
     utils::get_scale,
 };
 use crate::main_menu::button::Button;
+use crate::main_menu::scenario_picker::ScenarioPicker;
 use crate::RLResult;
 
 use crate::backend::screen::{Popup, ScreenCommand};
-use crate::game_core::infoscreen::InfoScreen;
 use crate::languages::{button_text, resume_error_string, Lang};
 use ggez::{graphics, Context};
 use std::sync::mpsc::{channel, Receiver, Sender};
@@ -20,6 +21,7 @@ pub enum Message {
     NewGame,
     Resume,
     ChangeLanguage,
+    ChangeDifficulty,
 }
 
 /// Main menu screen of the game with buttons to start a new game, load a game or exit the game.
@@ -30,6 +32,8 @@ pub struct MainMenu {
     sender: Sender<Message>,
     screen_sender: Sender<StackCommand>,
     background_image: Option<graphics::Image>,
+    /// The difficulty a new game is started with
+    difficulty: Difficulty,
     lng: Lang,
 }
 
@@ -48,6 +52,7 @@ impl MainMenu {
             sender,
             screen_sender,
             background_image: None,
+            difficulty: Difficulty::default(),
             lng,
         };
         menu.load_buttons();
@@ -90,12 +95,27 @@ impl MainMenu {
         let lang_button = Button::new(
             button_text(lng)[3].to_string(),
             Message::ChangeLanguage,
-            sender,
+            sender.clone(),
             graphics::Rect::new(1322., 140. + 630., 450., 120.),
             RLColor::GREY,
             RLColor::DARK_GREY,
         );
-        self.buttons = vec![start_button, new_game_button, exit_button, lang_button];
+
+        let difficulty_button = Button::new(
+            format!("{}: {}", button_text(lng)[4], self.difficulty.t(lng)),
+            Message::ChangeDifficulty,
+            sender,
+            graphics::Rect::new(1322., 210., 450., 120.),
+            RLColor::GREY,
+            RLColor::DARK_GREY,
+        );
+        self.buttons = vec![
+            start_button,
+            new_game_button,
+            exit_button,
+            lang_button,
+            difficulty_button,
+        ];
     }
 }
 
@@ -126,7 +146,7 @@ impl Screen for MainMenu {
                     let cloned_sender = self.screen_sender.clone();
                     self.screen_sender
                         .send(StackCommand::Screen(ScreenCommand::Push(Box::new(
-                            InfoScreen::new_intro_screen(cloned_sender, lng),
+                            ScenarioPicker::new(cloned_sender, lng, self.difficulty),
                         ))))?;
                 }
                 Message::Resume => {
@@ -155,6 +175,10 @@ impl Screen for MainMenu {
                     }
                     self.load_buttons();
                 }
+                Message::ChangeDifficulty => {
+                    self.difficulty = self.difficulty.next();
+                    self.load_buttons();
+                }
             }
         }
         Ok(())
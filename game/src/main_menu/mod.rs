@@ -1,3 +1,4 @@
 pub(crate) mod button;
 #[allow(clippy::module_inception)]
 pub(crate) mod main_menu;
+pub(crate) mod scenario_picker;
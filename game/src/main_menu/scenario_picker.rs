@@ -0,0 +1,156 @@
+//! This File contains the `ScenarioPicker`, the screen used to choose the scenario of a new game
+use crate::backend::constants::{Difficulty, GameMode};
+use crate::backend::rlcolor::RLColor;
+use crate::backend::screen::{Screen, ScreenCommand, StackCommand};
+use crate::backend::utils::{get_draw_params, get_scale};
+use crate::game_core::infoscreen::InfoScreen;
+use crate::game_core::scenario::scenarios;
+use crate::languages::{scenario_picker, Lang};
+use crate::{draw, RLResult};
+use ggez::glam::Vec2;
+use ggez::graphics::{self, DrawMode, Mesh, Rect, Text, TextFragment};
+use ggez::winit::event::VirtualKeyCode;
+use ggez::Context;
+use std::sync::mpsc::Sender;
+
+/// Position of the first scenario in the list
+const LIST_POSITION: (f32, f32) = (300., 250.);
+/// Width of one scenario entry
+const ENTRY_WIDTH: f32 = 1320.;
+/// Height of one scenario entry
+const ENTRY_HEIGHT: f32 = 110.;
+
+/// Screen which lists every scenario with its description.
+/// The chosen scenario is started with the intro screen.
+#[derive(Debug)]
+pub struct ScenarioPicker {
+    /// Index of the currently selected scenario
+    selected: usize,
+    /// The difficulty the new game is started with
+    difficulty: Difficulty,
+    /// The mode the new game is started with, changed with M
+    mode: GameMode,
+    sender: Sender<StackCommand>,
+    lng: Lang,
+}
+
+impl ScenarioPicker {
+    /// Creates a new `ScenarioPicker` with the first scenario selected
+    /// # Arguments
+    /// * `sender` - The sender of the screen stack
+    /// * `lng` - The current language
+    /// * `difficulty` - The difficulty the new game is started with
+    pub(crate) fn new(sender: Sender<StackCommand>, lng: Lang, difficulty: Difficulty) -> Self {
+        Self {
+            selected: 0,
+            difficulty,
+            mode: GameMode::default(),
+            sender,
+            lng,
+        }
+    }
+}
+
+impl Screen for ScenarioPicker {
+    /// W and S change the selected scenario, M changes the mode, Enter starts it and Escape returns to the main menu
+    /// # Arguments
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// `RLResult` - Returns an `RLResult`.
+    fn update(&mut self, ctx: &mut Context) -> RLResult {
+        let count = scenarios().len();
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::W)
+            || ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Up)
+        {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::S)
+            || ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Down)
+        {
+            self.selected = (self.selected + 1) % count;
+        }
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::M) {
+            self.mode = self.mode.next();
+        }
+        if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Escape) {
+            self.sender.send(StackCommand::Screen(ScreenCommand::Pop))?;
+        } else if ctx.keyboard.is_key_just_pressed(VirtualKeyCode::Return) {
+            self.sender.send(StackCommand::Screen(ScreenCommand::Pop))?;
+            self.sender
+                .send(StackCommand::Screen(ScreenCommand::Push(Box::new(
+                    InfoScreen::new_intro_screen(
+                        self.sender.clone(),
+                        self.lng,
+                        self.difficulty,
+                        &scenarios()[self.selected].id,
+                        self.mode,
+                    ),
+                ))))?;
+        }
+        Ok(())
+    }
+
+    /// Draws the name and description of every scenario, the selected one is highlighted
+    /// # Arguments
+    /// * `ctx` - The ggez context
+    /// # Returns
+    /// `RLResult` - Returns an `RLResult`.
+    fn draw(&self, ctx: &mut Context) -> RLResult {
+        let scale = get_scale(ctx);
+        let mut canvas = graphics::Canvas::from_frame(ctx, RLColor::DARK_BLUE);
+        let text = scenario_picker(self.lng);
+
+        let mut title = Text::new(text[0]);
+        title.set_scale(60.);
+        draw!(canvas, &title, Vec2::new(LIST_POSITION.0, 120.), scale);
+        let mut mode = Text::new(format!("{}: {}", text[2], self.mode.t(self.lng)));
+        mode.set_scale(36.);
+        draw!(
+            canvas,
+            &mode,
+            Vec2::new(LIST_POSITION.0 + 900., 135.),
+            scale
+        );
+
+        for (i, scenario) in scenarios().iter().enumerate() {
+            let position = Vec2::new(LIST_POSITION.0, LIST_POSITION.1 + ENTRY_HEIGHT * i as f32);
+            let color = if i == self.selected {
+                RLColor::LIGHT_GREY
+            } else {
+                RLColor::GREY
+            };
+            let background = Mesh::new_rounded_rectangle(
+                ctx,
+                DrawMode::fill(),
+                Rect::new(-15., -10., ENTRY_WIDTH, ENTRY_HEIGHT - 15.),
+                10.,
+                color,
+            )?;
+            draw!(canvas, &background, position, scale);
+            let mut name =
+                Text::new(TextFragment::new(scenario.name.get(self.lng)).color(RLColor::BLACK));
+            name.set_scale(36.);
+            draw!(canvas, &name, position, scale);
+            let mut description = Text::new(
+                TextFragment::new(scenario.description.get(self.lng)).color(RLColor::BLACK),
+            );
+            description.set_scale(24.);
+            draw!(canvas, &description, position + Vec2::new(0., 45.), scale);
+        }
+
+        let mut controls = Text::new(text[1]);
+        controls.set_scale(28.);
+        draw!(canvas, &controls, Vec2::new(LIST_POSITION.0, 950.), scale);
+
+        canvas.finish(ctx)?;
+        Ok(())
+    }
+
+    fn set_sender(&mut self, sender: Sender<StackCommand>) {
+        self.sender = sender;
+    }
+
+    fn lang(&self) -> Lang {
+        self.lng
+    }
+}